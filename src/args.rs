@@ -3,13 +3,17 @@
 use clap::{ArgAction, ArgGroup, Parser, ValueEnum};
 use std::path::PathBuf;
 
-use crate::model::{CLIOptions, OutlineFormat, RunMode};
+use crate::model::{
+    CLIOptions, ConfigFormat, GraphFormat, OutlineDetail, OutlineDocs, OutlineFormat,
+    OutlineLayout, OutputFormat, RunMode, SplitLimit, Truncate,
+};
 
 /// outline サブオプション
 #[derive(Debug, Clone, ValueEnum)]
 pub enum ModeArg {
     Gather,
     Outline,
+    Graph,
 }
 
 #[derive(Debug, Clone, ValueEnum)]
@@ -18,6 +22,47 @@ pub enum FormatArg {
     Json,
 }
 
+#[derive(Debug, Clone, ValueEnum)]
+pub enum OutputFormatArg {
+    Markdown,
+    Xml,
+    Json,
+    Jsonl,
+}
+
+#[derive(Debug, Clone, ValueEnum)]
+pub enum TruncateArg {
+    Head,
+    Tail,
+    HeadTail,
+}
+
+#[derive(Debug, Clone, ValueEnum)]
+pub enum GraphFormatArg {
+    Dot,
+    Mermaid,
+    Json,
+}
+
+#[derive(Debug, Clone, ValueEnum)]
+pub enum DetailArg {
+    Names,
+    Signatures,
+}
+
+#[derive(Debug, Clone, ValueEnum)]
+pub enum LayoutArg {
+    Tree,
+    Flat,
+}
+
+#[derive(Debug, Clone, ValueEnum)]
+pub enum DocsArg {
+    Summary,
+    Full,
+    None,
+}
+
 /// 内部用 – clap 派生構造体
 #[derive(Debug, Parser)]
 #[command(
@@ -31,10 +76,15 @@ pub enum FormatArg {
         ArgGroup::new("outline_opts")
             .requires("mode")
             .args(["outline_format"])
+    ),
+    group(
+        ArgGroup::new("graph_opts")
+            .requires("mode")
+            .args(["graph_format"])
     )
 )]
 struct Args {
-    /// 実行モード: gather (既定) / outline
+    /// 実行モード: gather (既定) / outline / graph
     #[arg(long, value_enum, value_name = "MODE", default_value = "gather")]
     mode: ModeArg,
 
@@ -42,6 +92,30 @@ struct Args {
     #[arg(long = "outline-format", value_enum, value_name = "FMT")]
     outline_format: Option<FormatArg>,
 
+    /// graph 時のフォーマット: dot (既定) / mermaid / json
+    #[arg(long = "graph-format", value_enum, value_name = "FMT")]
+    graph_format: Option<GraphFormatArg>,
+
+    #[command(flatten)]
+    common: Common,
+}
+
+/// gather / outline / graph と `gather config` で共有するオプション
+#[derive(Debug, clap::Args)]
+struct Common {
+    /// アウトラインの詳細度: names (既定) / signatures  
+    /// outline モードと `[outline]` セクションの両方に効く
+    #[arg(long = "outline-detail", value_enum, value_name = "LEVEL")]
+    outline_detail: Option<DetailArg>,
+
+    /// アウトラインの並べ方: tree (既定、入れ子) / flat (旧来の平坦リスト)
+    #[arg(long = "outline-layout", value_enum, value_name = "LAYOUT")]
+    outline_layout: Option<LayoutArg>,
+
+    /// doc コメントの出力: summary (既定、最初の段落) / full (全文) / none
+    #[arg(long = "outline-docs", value_enum, value_name = "DOCS")]
+    outline_docs: Option<DocsArg>,
+
     /// 解析対象ディレクトリ
     #[arg(value_name = "DIR")]
     target_directory: PathBuf,
@@ -67,6 +141,49 @@ struct Args {
     no_open: bool,
     #[arg(long, action = ArgAction::SetTrue)]
     use_gitignore: bool,
+    /// トークン数を数える tiktoken 形式の語彙ファイル (未指定なら chars/4 で見積もり)
+    #[arg(long, value_name = "FILE")]
+    token_vocab: Option<PathBuf>,
+    /// ツリーに各ファイル・ディレクトリのトークン数を注記する
+    #[arg(long, action = ArgAction::SetTrue)]
+    tree_tokens: bool,
+    /// 本文の各行に行番号を付ける
+    #[arg(long, action = ArgAction::SetTrue)]
+    line_numbers: bool,
+    /// max_lines を超えた本文の残し方: head (既定) / tail / head-tail
+    #[arg(long, value_enum, value_name = "STRATEGY")]
+    truncate: Option<TruncateArg>,
+    /// 出力全体のトークン上限。超える分は outline → ツリーのみに縮退させる
+    #[arg(long, value_name = "N")]
+    max_tokens: Option<usize>,
+    /// 上限ごとに output_part1.txt … へ分割: 500kb / 30000tokens / 2000lines
+    #[arg(long, value_name = "LIMIT")]
+    split: Option<SplitLimit>,
+    /// gather 時の出力形式: markdown (既定) / xml / json / jsonl
+    #[arg(long = "output-format", value_enum, value_name = "FMT")]
+    output_format: Option<OutputFormatArg>,
+}
+
+/// `gather config` の出力形式
+#[derive(Debug, Clone, ValueEnum)]
+pub enum ConfigFormatArg {
+    Text,
+    Json,
+}
+
+/// `gather config` – 実効設定と各値の出どころを表示
+#[derive(Debug, Parser)]
+#[command(
+    name = "gather config",
+    about = "Print the effective settings and where each value comes from."
+)]
+struct ConfigArgs {
+    /// 出力形式: text (既定) / json
+    #[arg(long, value_enum, value_name = "FMT", default_value = "text")]
+    format: ConfigFormatArg,
+
+    #[command(flatten)]
+    common: Common,
 }
 
 /// 既存 API 互換ラッパ
@@ -80,8 +197,26 @@ pub fn parse_args() -> CLIOptions {
     let mode = match a.mode {
         ModeArg::Gather => RunMode::Gather,
         ModeArg::Outline => RunMode::Outline(format),
+        ModeArg::Graph => RunMode::Graph(match a.graph_format.unwrap_or(GraphFormatArg::Dot) {
+            GraphFormatArg::Dot => GraphFormat::Dot,
+            GraphFormatArg::Mermaid => GraphFormat::Mermaid,
+            GraphFormatArg::Json => GraphFormat::Json,
+        }),
     };
+    options(mode, a.common)
+}
 
+/// `gather config …` の引数 (先頭の `config` は読み飛ばす)
+pub fn parse_config_args() -> (CLIOptions, ConfigFormat) {
+    let a = ConfigArgs::parse_from(std::env::args().skip(1));
+    let format = match a.format {
+        ConfigFormatArg::Text => ConfigFormat::Text,
+        ConfigFormatArg::Json => ConfigFormat::Json,
+    };
+    (options(RunMode::Gather, a.common), format)
+}
+
+fn options(mode: RunMode, a: Common) -> CLIOptions {
     CLIOptions {
         mode,
         target_dir: a.target_directory,
@@ -95,6 +230,35 @@ pub fn parse_args() -> CLIOptions {
         use_timestamp: a.timestamp,
         no_open: a.no_open,
         use_gitignore: a.use_gitignore,
+        outline_detail: a.outline_detail.map(|d| match d {
+            DetailArg::Names => OutlineDetail::Names,
+            DetailArg::Signatures => OutlineDetail::Signatures,
+        }),
+        outline_layout: a.outline_layout.map(|l| match l {
+            LayoutArg::Tree => OutlineLayout::Tree,
+            LayoutArg::Flat => OutlineLayout::Flat,
+        }),
+        outline_docs: a.outline_docs.map(|d| match d {
+            DocsArg::Summary => OutlineDocs::Summary,
+            DocsArg::Full => OutlineDocs::Full,
+            DocsArg::None => OutlineDocs::None,
+        }),
+        token_vocab: a.token_vocab,
+        tree_tokens: a.tree_tokens,
+        line_numbers: a.line_numbers,
+        truncate: a.truncate.map(|t| match t {
+            TruncateArg::Head => Truncate::Head,
+            TruncateArg::Tail => Truncate::Tail,
+            TruncateArg::HeadTail => Truncate::HeadTail,
+        }),
+        max_tokens: a.max_tokens,
+        split: a.split,
+        output_format: a.output_format.map(|f| match f {
+            OutputFormatArg::Markdown => OutputFormat::Markdown,
+            OutputFormatArg::Xml => OutputFormat::Xml,
+            OutputFormatArg::Json => OutputFormat::Json,
+            OutputFormatArg::Jsonl => OutputFormat::Jsonl,
+        }),
     }
 }
 
@@ -125,4 +289,38 @@ mod tests {
         assert!(matches!(args.mode, ModeArg::Outline));
         assert!(matches!(args.outline_format, Some(FormatArg::Json)));
     }
+
+    #[test]
+    fn outline_detail_parses_without_outline_mode() {
+        let args = Args::try_parse_from(["gather", "--outline-detail", "signatures", "."]).unwrap();
+        assert!(matches!(
+            args.common.outline_detail,
+            Some(DetailArg::Signatures)
+        ));
+    }
+
+    #[test]
+    fn graph_mode_parses_with_format() {
+        let args = Args::try_parse_from([
+            "gather",
+            "--mode",
+            "graph",
+            "--graph-format",
+            "mermaid",
+            ".",
+        ])
+        .unwrap();
+        assert!(matches!(args.mode, ModeArg::Graph));
+        assert!(matches!(args.graph_format, Some(GraphFormatArg::Mermaid)));
+    }
+
+    #[test]
+    fn config_args_share_common_options() {
+        let args =
+            ConfigArgs::try_parse_from(["gather config", "--format", "json", "-m", "50", "."])
+                .unwrap();
+        assert!(matches!(args.format, ConfigFormatArg::Json));
+        assert_eq!(args.common.max_lines, Some(50));
+        assert_eq!(args.common.target_directory, PathBuf::from("."));
+    }
 }
@@ -1,11 +1,48 @@
 //! `.gather` 設定ファイルパーサ
 //! セクション見出しの末尾 `]` 以降にコメント／空白があっても許容する。
+//! `[exclude]` / `[skip]` / `[outline]` では `!pattern` で再包含でき、後に書いた行ほど優先。
+//! サブディレクトリの `.gather` は `load_scoped_config` で親の設定に重ねる。
+//!
+//! `[settings]` は型付きスキーマ (`Settings`) に serde で読み込み、型の合わない値・未知のキー・
+//! 重複キーは `ファイル:行: 内容` の形で警告する。従来の `yes` / 裸の文字列もそのまま読める。
 
-use std::{collections::HashMap, fs, path::Path};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    path::Path,
+    path::PathBuf,
+    str::FromStr,
+};
 
-use crate::model::ConfigParams;
+use serde::de::{self, Deserializer};
+use serde::Deserialize;
+
+use crate::model::{
+    ConfigParams, OutlineDetail, OutlineDocs, OutlineLayout, OutputFormat, Source, SplitHeader,
+    SplitLimit, Truncate, TruncateRule,
+};
+
+/// サブディレクトリの `.gather` で上書きできる設定キー (ファイル単位で効くものだけ)
+const SCOPED_SETTINGS: &[&str] = &[
+    "max_lines",
+    "max_file_size",
+    "skip_binary",
+    "line_numbers",
+    "truncate",
+    "outline_detail",
+    "outline_layout",
+    "outline_docs",
+];
+
+/// 設定ファイルの問題点 (行番号は 1 始まり)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub line: usize,
+    pub message: String,
+}
 
 /// 読み込み。存在しなければ `default()` を返す。
+/// 型の合わない値・未知のキー・重複キーは行番号付きで警告し、その行を無視する。
 pub fn load_config_file(path: &Path) -> ConfigParams {
     let content = match fs::read_to_string(path) {
         Ok(s) => s,
@@ -13,51 +50,242 @@ pub fn load_config_file(path: &Path) -> ConfigParams {
     };
 
     let mut params = ConfigParams::default();
+    let diagnostics = parse_into(&mut params, &content, None);
+    report(path, &diagnostics);
+    params
+}
 
-    /* ---------- settings キー → 更新クロージャ ---------- */
-    type Setter = fn(&mut ConfigParams, &str);
-    let mut map: HashMap<&str, Setter> = HashMap::new();
-    macro_rules! set_bool {
-        ($field:ident) => {
-            |p: &mut ConfigParams, v: &str| {
-                p.$field = matches!(v.trim().to_lowercase().as_str(), "yes" | "true" | "1");
-            }
-        };
+/// サブディレクトリの `.gather` を読み込む。
+///
+/// 設定は `parent` (親ディレクトリの実効設定) を引き継ぎ、`SCOPED_SETTINGS` のうち
+/// CLI で指定されていないものだけを上書きする。パターン類は引き継がず、
+/// このファイルに書かれたもの (そのディレクトリからの相対パス) だけを持つ。
+pub fn load_scoped_config(path: &Path, parent: &ConfigParams) -> ConfigParams {
+    let mut params = ConfigParams {
+        exclude_patterns: vec![],
+        skip_content_patterns: vec![],
+        include_patterns: vec![],
+        outline_patterns: vec![],
+        priority_patterns: vec![],
+        language_overrides: vec![],
+        truncate_rules: vec![],
+        ..parent.clone()
+    };
+    // リストの要素の出どころは親のもの
+    params.sources.retain(|k, _| !k.contains('['));
+    let Ok(content) = fs::read_to_string(path) else {
+        return params;
+    };
+
+    let diagnostics = parse_into(&mut params, &content, Some(parent));
+    report(path, &diagnostics);
+    params
+}
+
+fn report(path: &Path, diagnostics: &[Diagnostic]) {
+    for d in diagnostics {
+        match d.line {
+            0 => eprintln!("{}: {}", path.display(), d.message),
+            n => eprintln!("{}:{n}: {}", path.display(), d.message),
+        }
+    }
+}
+
+/* ---------- [settings] のスキーマ ---------- */
+
+/// `[settings]` の型付きスキーマ。
+///
+/// 値は TOML の型 (`1000` / `true` / `"gather"`) でも、従来の裸の文字列
+/// (`yes` / `gather` / `500kb`) でもよい。どちらも各型の `FromStr` で解釈する。
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Settings {
+    #[serde(deserialize_with = "lenient")]
+    max_lines: Option<usize>,
+    #[serde(deserialize_with = "lenient")]
+    max_file_size: Option<u64>,
+    #[serde(deserialize_with = "lenient")]
+    skip_binary: Option<Flag>,
+    #[serde(deserialize_with = "lenient")]
+    output_dir: Option<String>,
+    #[serde(deserialize_with = "lenient")]
+    use_timestamp: Option<Flag>,
+    #[serde(deserialize_with = "lenient")]
+    open_output: Option<Flag>,
+    #[serde(deserialize_with = "lenient")]
+    use_gitignore: Option<Flag>,
+    #[serde(deserialize_with = "lenient")]
+    first_run_completed: Option<Flag>,
+    #[serde(deserialize_with = "lenient")]
+    max_files_per_dir: Option<usize>,
+    #[serde(deserialize_with = "lenient")]
+    max_auto_file_size: Option<u64>,
+    #[serde(deserialize_with = "lenient")]
+    outline_detail: Option<OutlineDetail>,
+    #[serde(deserialize_with = "lenient")]
+    outline_layout: Option<OutlineLayout>,
+    #[serde(deserialize_with = "lenient")]
+    outline_docs: Option<OutlineDocs>,
+    #[serde(deserialize_with = "lenient")]
+    token_vocab: Option<PathBuf>,
+    #[serde(deserialize_with = "lenient")]
+    token_top_n: Option<usize>,
+    #[serde(deserialize_with = "lenient")]
+    tree_tokens: Option<Flag>,
+    #[serde(deserialize_with = "lenient")]
+    line_numbers: Option<Flag>,
+    #[serde(deserialize_with = "lenient")]
+    truncate: Option<Truncate>,
+    #[serde(deserialize_with = "lenient")]
+    max_tokens: Option<usize>,
+    #[serde(deserialize_with = "lenient")]
+    split: Option<SplitLimit>,
+    #[serde(deserialize_with = "lenient")]
+    output_format: Option<OutputFormat>,
+    #[serde(deserialize_with = "lenient")]
+    split_header: Option<SplitHeader>,
+}
+
+impl Settings {
+    /// 指定されたキーだけを `p` に反映する
+    fn apply(self, p: &mut ConfigParams) {
+        macro_rules! set {
+            ($($field:ident),* $(,)?) => {$(
+                if let Some(v) = self.$field {
+                    p.$field = v.into();
+                }
+            )*};
+        }
+        set!(
+            max_lines,
+            max_file_size,
+            skip_binary,
+            output_dir,
+            use_timestamp,
+            open_output,
+            use_gitignore,
+            first_run_completed,
+            max_files_per_dir,
+            max_auto_file_size,
+            outline_detail,
+            outline_layout,
+            outline_docs,
+            token_vocab,
+            token_top_n,
+            tree_tokens,
+            line_numbers,
+            truncate,
+            max_tokens,
+            split,
+            output_format,
+            split_header,
+        );
+    }
+}
+
+/// `yes` / `no` / `true` / `false` / `1` / `0` / `on` / `off`
+#[derive(Debug)]
+struct Flag(bool);
+
+impl From<Flag> for bool {
+    fn from(f: Flag) -> bool {
+        f.0
+    }
+}
+
+impl FromStr for Flag {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "yes" | "true" | "1" | "on" => Ok(Flag(true)),
+            "no" | "false" | "0" | "off" => Ok(Flag(false)),
+            _ => Err("expected yes / no".into()),
+        }
+    }
+}
+
+/// 文字列・整数・真偽値のいずれでも受け取り、`T::from_str` で解釈する
+fn lenient<'de, D, T>(d: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: std::fmt::Display,
+{
+    struct Text;
+    impl de::Visitor<'_> for Text {
+        type Value = String;
+
+        fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.write_str("a string, integer or boolean")
+        }
+        fn visit_str<E: de::Error>(self, v: &str) -> Result<String, E> {
+            Ok(v.to_string())
+        }
+        fn visit_i64<E: de::Error>(self, v: i64) -> Result<String, E> {
+            Ok(v.to_string())
+        }
+        fn visit_u64<E: de::Error>(self, v: u64) -> Result<String, E> {
+            Ok(v.to_string())
+        }
+        fn visit_bool<E: de::Error>(self, v: bool) -> Result<String, E> {
+            Ok(v.to_string())
+        }
     }
 
-    map.insert("max_lines", |p, v| {
-        p.max_lines = v.parse().unwrap_or(p.max_lines)
-    });
-    map.insert("max_file_size", |p, v| p.max_file_size = v.parse().ok());
-    map.insert("skip_binary", set_bool!(skip_binary));
-    map.insert("output_dir", |p, v| {
-        if !v.is_empty() {
-            p.output_dir = Some(v.to_string())
+    let text = d.deserialize_any(Text)?;
+    text.parse()
+        .map(Some)
+        .map_err(|e| de::Error::custom(format!("`{text}`: {e}")))
+}
+
+/// `key = value` の値を TOML として読む。TOML でなければ `#` 以降を除いた裸の文字列とみなす。
+fn settings_value(raw: &str) -> toml::Value {
+    match toml::from_str::<toml::Table>(&format!("v = {raw}")) {
+        Ok(mut t) => t.remove("v").unwrap_or(toml::Value::String(String::new())),
+        Err(_) => {
+            let bare = raw.split('#').next().unwrap_or("").trim();
+            toml::Value::String(bare.to_string())
         }
-    });
-    map.insert("use_timestamp", set_bool!(use_timestamp));
-    map.insert("open_output", set_bool!(open_output));
-    map.insert("use_gitignore", set_bool!(use_gitignore));
-    map.insert("first_run_completed", set_bool!(first_run_completed));
-    map.insert("max_files_per_dir", |p, v| {
-        p.max_files_per_dir = v.parse().unwrap_or(p.max_files_per_dir)
-    });
-    map.insert("max_auto_file_size", |p, v| {
-        p.max_auto_file_size = v.parse().unwrap_or(p.max_auto_file_size)
-    });
-
-    /* ---------- 行ループ ---------- */
+    }
+}
+
+/* ---------- 行ループ ---------- */
+
+/// `content` を `params` に重ねる。
+///
+/// `parent` はサブディレクトリの `.gather` を読むときの親の実効設定。
+/// その場合 `SCOPED_SETTINGS` 以外のキーと `[priority]` は警告して無視し、
+/// CLI で指定済みのキーはルートと同じく黙って CLI を優先する。
+fn parse_into(
+    params: &mut ConfigParams,
+    content: &str,
+    parent: Option<&ConfigParams>,
+) -> Vec<Diagnostic> {
     enum Section {
         None,
+        Unknown,
         Settings,
         Exclude,
         Skip,
         Include,
         Outline,
+        Priority,
+        Languages,
+        Truncate,
     }
     let mut section = Section::None;
+    let mut diagnostics = Vec::new();
+    let mut seen: HashMap<String, usize> = HashMap::new();
 
-    for raw in content.lines() {
+    for (idx, raw) in content.lines().enumerate() {
+        let lineno = idx + 1;
+        let mut warn = |message: String| {
+            diagnostics.push(Diagnostic {
+                line: lineno,
+                message,
+            })
+        };
         let line = raw.trim();
         if line.is_empty() || line.starts_with('#') {
             continue;
@@ -65,13 +293,24 @@ pub fn load_config_file(path: &Path) -> ConfigParams {
 
         if line.starts_with('[') {
             if let Some(end) = line.find(']') {
-                section = match &line[1..end].trim().to_lowercase()[..] {
+                let name = line[1..end].trim().to_lowercase();
+                section = match &name[..] {
                     "settings" => Section::Settings,
                     "exclude" => Section::Exclude,
                     "skip" => Section::Skip,
                     "include" => Section::Include,
                     "outline" => Section::Outline,
-                    _ => Section::None,
+                    "priority" if parent.is_some() => {
+                        warn("[priority] はサブディレクトリの .gather では無視されます".into());
+                        Section::Unknown
+                    }
+                    "priority" => Section::Priority,
+                    "languages" => Section::Languages,
+                    "truncate" => Section::Truncate,
+                    _ => {
+                        warn(format!("不明なセクション [{name}] (中身は無視します)"));
+                        Section::Unknown
+                    }
                 };
                 continue;
             }
@@ -79,45 +318,191 @@ pub fn load_config_file(path: &Path) -> ConfigParams {
 
         match section {
             Section::Settings => {
-                if let Some((k, v)) = line.split_once('=') {
-                    if let Some(set) = map.get(k.trim().to_lowercase().as_str()) {
-                        set(&mut params, v.trim());
+                let Some((k, v)) = line.split_once('=') else {
+                    warn(format!("`key = value` の形ではありません: {line}"));
+                    continue;
+                };
+                let key = k.trim().to_lowercase();
+                let value = settings_value(v.trim());
+                if value.as_str() == Some("") {
+                    continue; // 値なしは未指定扱い
+                }
+                let entry = toml::Table::from_iter([(key.clone(), value)]);
+                match toml::Value::Table(entry).try_into::<Settings>() {
+                    Ok(settings) => {
+                        if let Some(first) = seen.insert(key.clone(), lineno) {
+                            warn(format!(
+                                "`{key}` が {first} 行目と重複しています (後の値を使います)"
+                            ));
+                        }
+                        let not_scoped =
+                            parent.is_some() && !SCOPED_SETTINGS.contains(&key.as_str());
+                        let cli_wins =
+                            parent.is_some_and(|p| p.sources.get(&key) == Some(&Source::Cli));
+                        if not_scoped {
+                            warn(format!(
+                                "`{key}` はサブディレクトリの .gather では無視されます"
+                            ));
+                        } else if !cli_wins {
+                            settings.apply(params);
+                            params.sources.insert(key, Source::Gather { line: lineno });
+                        }
                     }
+                    Err(e) => warn(format!("[settings] {key}: {} (無視します)", e.message())),
                 }
             }
-            Section::Exclude => push_pattern(&mut params.exclude_patterns, line),
-            Section::Skip => push_pattern(&mut params.skip_content_patterns, line),
-            Section::Include => push_pattern(&mut params.include_patterns, line),
-            Section::Outline => push_pattern(&mut params.outline_patterns, line),
-            Section::None => {}
+            Section::Exclude => push_pattern(
+                &mut params.exclude_patterns,
+                &mut params.sources,
+                "exclude_patterns",
+                line,
+                lineno,
+            ),
+            Section::Skip => push_pattern(
+                &mut params.skip_content_patterns,
+                &mut params.sources,
+                "skip_content_patterns",
+                line,
+                lineno,
+            ),
+            Section::Include => push_pattern(
+                &mut params.include_patterns,
+                &mut params.sources,
+                "include_patterns",
+                line,
+                lineno,
+            ),
+            Section::Outline => push_pattern(
+                &mut params.outline_patterns,
+                &mut params.sources,
+                "outline_patterns",
+                line,
+                lineno,
+            ),
+            Section::Priority => push_pattern(
+                &mut params.priority_patterns,
+                &mut params.sources,
+                "priority_patterns",
+                line,
+                lineno,
+            ),
+            Section::Languages => {
+                // `パターン = 言語` (値が空なら言語なし)
+                let entry = line.split('#').next().unwrap_or("");
+                match entry.split_once('=') {
+                    Some((pat, lang)) if !pat.trim().is_empty() => {
+                        let key =
+                            format!("language_overrides[{}]", params.language_overrides.len());
+                        params.sources.insert(key, Source::Gather { line: lineno });
+                        params
+                            .language_overrides
+                            .push((pat.trim().to_string(), lang.trim().to_string()));
+                    }
+                    _ => warn(format!("[languages] は `パターン = 言語` の形です: {line}")),
+                }
+            }
+            Section::Truncate => {
+                // `パターン = 方式 [行数]`
+                let entry = line.split('#').next().unwrap_or("");
+                let Some((pat, rule)) = entry.split_once('=').filter(|(p, _)| !p.trim().is_empty())
+                else {
+                    warn(format!(
+                        "[truncate] は `パターン = 方式 [行数]` の形です: {line}"
+                    ));
+                    continue;
+                };
+                let mut words = rule.split_whitespace();
+                let strategy = words.next().unwrap_or("").parse::<Truncate>();
+                let max_lines = words.next().map(str::parse::<usize>).transpose();
+                match (strategy, max_lines) {
+                    (Ok(strategy), Ok(max_lines)) => {
+                        let key = format!("truncate_rules[{}]", params.truncate_rules.len());
+                        params.sources.insert(key, Source::Gather { line: lineno });
+                        params.truncate_rules.push(TruncateRule {
+                            pattern: pat.trim().to_string(),
+                            strategy,
+                            max_lines,
+                        });
+                    }
+                    (Err(e), _) => warn(format!("[truncate] {}: {e} (無視します)", pat.trim())),
+                    (_, Err(e)) => {
+                        warn(format!("[truncate] {}: 行数 {e} (無視します)", pat.trim()))
+                    }
+                }
+            }
+            Section::Unknown => {}
+            Section::None => warn(format!("セクションの外にある行を無視します: {line}")),
         }
     }
-    params
+    diagnostics
 }
 
-fn push_pattern(vec: &mut Vec<String>, line: &str) {
+/// パターンを 1 つ積み、出どころ (`name[i]` → 行番号) を記録する
+fn push_pattern(
+    vec: &mut Vec<String>,
+    sources: &mut BTreeMap<String, Source>,
+    name: &str,
+    line: &str,
+    lineno: usize,
+) {
     let pat = line.split('#').next().unwrap_or("").trim();
-    if !pat.is_empty() {
-        vec.push(pat.to_string());
-    }
+    let pat = match pat.strip_prefix('!').map(str::trim_start) {
+        // `! pattern` も `!pattern` として扱い、`!` だけの行は捨てる
+        Some("") => return,
+        Some(rest) => format!("!{rest}"),
+        None if !pat.is_empty() => pat.to_string(),
+        None => return,
+    };
+    sources.insert(
+        format!("{name}[{}]", vec.len()),
+        Source::Gather { line: lineno },
+    );
+    vec.push(pat);
 }
 
 /* -------------------------------------------------------------------- */
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::model::{
+        OutlineDetail, OutlineDocs, OutputFormat, SplitHeader, SplitLimit, Truncate,
+    };
     use std::io::Write;
     use tempfile::NamedTempFile;
 
     const SAMPLE: &str = r#"
 [settings]
 use_gitignore = yes
+outline_detail = signatures
+outline_docs = full
+tree_tokens = yes
+line_numbers = yes
+truncate = head_tail
+token_top_n = 3
+max_tokens = 120000
+split = 500kb
+split_header = index
+output_format = xml
 
 [outline]
 *.rs
 
 [exclude]
 node_modules/
+tests/fixtures/
+! tests/fixtures/README.md   # 再包含
+
+[priority]
+src/lib.rs
+*.md
+
+[languages]
+*.tpl = html   # テンプレート
+Justfile = make
+
+[truncate]
+*.log = tail 200
+tests/** = head-tail
 "#;
 
     #[test]
@@ -126,5 +511,130 @@ node_modules/
         write!(tmp, "{}", SAMPLE).unwrap();
         let cfg = load_config_file(tmp.path());
         assert_eq!(cfg.outline_patterns, vec!["*.rs"]);
+        assert_eq!(
+            cfg.exclude_patterns,
+            vec![
+                "node_modules/",
+                "tests/fixtures/",
+                "!tests/fixtures/README.md"
+            ]
+        );
+        assert_eq!(cfg.outline_detail, OutlineDetail::Signatures);
+        assert_eq!(cfg.outline_docs, OutlineDocs::Full);
+        assert_eq!(
+            cfg.language_overrides,
+            vec![
+                ("*.tpl".to_string(), "html".to_string()),
+                ("Justfile".to_string(), "make".to_string())
+            ]
+        );
+        assert!(cfg.tree_tokens);
+        assert!(cfg.line_numbers);
+        assert_eq!(cfg.truncate, Truncate::HeadTail);
+        assert_eq!(
+            cfg.truncate_rules,
+            vec![
+                TruncateRule {
+                    pattern: "*.log".into(),
+                    strategy: Truncate::Tail,
+                    max_lines: Some(200),
+                },
+                TruncateRule {
+                    pattern: "tests/**".into(),
+                    strategy: Truncate::HeadTail,
+                    max_lines: None,
+                },
+            ]
+        );
+        assert_eq!(cfg.token_top_n, 3);
+        assert_eq!(cfg.max_tokens, Some(120_000));
+        assert_eq!(cfg.split, Some(SplitLimit::Bytes(500_000)));
+        assert_eq!(cfg.split_header, SplitHeader::Index);
+        assert_eq!(cfg.output_format, OutputFormat::Xml);
+        assert_eq!(cfg.priority_patterns, vec!["src/lib.rs", "*.md"]);
+    }
+
+    #[test]
+    fn bad_values_unknown_and_duplicate_keys_are_reported_by_line() {
+        let mut cfg = ConfigParams::default();
+        let diags = parse_into(
+            &mut cfg,
+            "[settings]\nmax_lines = abc\nmax_line = 5\nopen_output = no\nopen_output = yes\n\
+             max_tokens = 1_000   # TOML の整数\noutput_dir = \"out\"\n[setings]\nx = 1\n",
+            None,
+        );
+        let lines: Vec<_> = diags.iter().map(|d| d.line).collect();
+        assert_eq!(lines, vec![2, 3, 5, 8], "{diags:#?}");
+        assert!(diags[0].message.contains("max_lines"));
+        assert!(diags[1].message.contains("unknown field `max_line`"));
+        assert!(diags[2].message.contains("4 行目"));
+        assert_eq!(cfg.max_lines, 1000, "bad value keeps the default");
+        assert!(cfg.open_output, "last duplicate wins");
+        assert_eq!(cfg.max_tokens, Some(1000));
+        assert_eq!(cfg.output_dir.as_deref(), Some("out"));
+    }
+
+    #[test]
+    fn default_template_has_no_diagnostics() {
+        let mut cfg = ConfigParams::default();
+        let diags = parse_into(
+            &mut cfg,
+            include_str!("templates/gather_default.toml"),
+            None,
+        );
+        assert_eq!(diags, vec![]);
+        assert!(cfg.skip_binary);
+        assert_eq!(cfg.max_file_size, Some(500_000));
+    }
+
+    #[test]
+    fn nested_gather_warns_only_for_unscoped_keys_and_priority() {
+        let parent = ConfigParams {
+            max_lines: 50,
+            sources: [("max_lines".to_string(), Source::Cli)].into(),
+            ..ConfigParams::default()
+        };
+        let mut cfg = parent.clone();
+        let diags = parse_into(
+            &mut cfg,
+            "[settings]\nmax_lines = 2\noutput_dir = out\nskip_binary = yes\n\n\
+             [priority]\nsrc/lib.rs\n",
+            Some(&parent),
+        );
+        let lines: Vec<_> = diags.iter().map(|d| d.line).collect();
+        assert_eq!(
+            lines,
+            vec![3, 6],
+            "CLI-overridden key stays silent: {diags:#?}"
+        );
+        assert!(diags[0].message.contains("output_dir"));
+        assert!(diags[1].message.contains("[priority]"));
+        assert_eq!(cfg.max_lines, 50, "CLI wins");
+        assert!(cfg.skip_binary);
+        assert!(cfg.priority_patterns.is_empty());
+        assert!(!cfg
+            .sources
+            .keys()
+            .any(|k| k.starts_with("priority_patterns")));
+    }
+
+    #[test]
+    fn scoped_config_keeps_cli_values_and_drops_priority() {
+        let mut tmp = NamedTempFile::new().unwrap();
+        write!(
+            tmp,
+            "[settings]\nmax_lines = 2\noutput_dir = out\nskip_binary = yes\n\n[priority]\nsrc/lib.rs\n"
+        )
+        .unwrap();
+        let parent = ConfigParams {
+            max_lines: 50,
+            sources: [("max_lines".to_string(), Source::Cli)].into(),
+            ..ConfigParams::default()
+        };
+        let cfg = load_scoped_config(tmp.path(), &parent);
+        assert_eq!(cfg.max_lines, 50, "CLI wins");
+        assert_eq!(cfg.output_dir, None, "not a scoped key");
+        assert!(cfg.skip_binary);
+        assert!(cfg.priority_patterns.is_empty());
     }
 }
@@ -4,12 +4,12 @@
 //! CLI からは lib::run() 経由で呼び出される。
 
 use crate::config::load_config_file;
-use crate::gitignore::parse_gitignore;
-use crate::model::{CLIOptions as GatherOptions, ConfigParams};
+use crate::model::{CLIOptions as GatherOptions, ConfigParams, Source};
 use crate::scanner::run as scan_run;
 
 use anyhow::Context;
 use chrono::Local;
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::process::Command;
@@ -30,10 +30,7 @@ pub fn gather_files(opts: GatherOptions) -> anyhow::Result<PathBuf> {
     }
 
     /* --- .gather パス決定 --------------------------------------------- */
-    let gather_path = opts
-        .config_file
-        .clone()
-        .unwrap_or_else(|| opts.target_dir.join(".gather"));
+    let gather_path = config_path(&opts);
 
     /* ─────────────── 初回実行：テンプレ生成 ─────────────── */
     if !gather_path.exists() {
@@ -44,30 +41,17 @@ pub fn gather_files(opts: GatherOptions) -> anyhow::Result<PathBuf> {
     }
 
     /* --- 設定読み込み & CLI 反映 -------------------------------------- */
-    let mut cfg = load_config_file(&gather_path);
-    merge_cli_into_config(&opts, &mut cfg)?;
-
-    /* --- .gitignore 取り込み (オプション) ------------------------------ */
-    if cfg.use_gitignore {
-        let gi = opts.target_dir.join(".gitignore");
-        if gi.exists() {
-            if let Ok(pats) = parse_gitignore(&gi) {
-                for p in pats.into_iter().filter(|p| !p.is_empty()) {
-                    if !cfg.exclude_patterns.contains(&p) {
-                        cfg.exclude_patterns.push(p);
-                    }
-                }
-            }
-        }
-    }
+    let cfg = load_effective_config(&opts)?;
 
     /* --- 出力パス決定 -------------------------------------------------- */
-    let output_path = determine_output_path(&opts, &cfg)?;
+    let output_path = determine_output_path(&opts, &cfg, cfg.output_format.extension())?;
 
     /* --- 走査 ---------------------------------------------------------- */
-    scan_run(&opts.target_dir, &output_path, &cfg, &[])
+    // 分割された場合は先頭のパートを返す
+    let written = scan_run(&opts.target_dir, &output_path, &cfg, &[])
         .map_err(|e| anyhow::anyhow!(e))
         .context("scanner failed")?;
+    let output_path = written.into_iter().next().unwrap_or(output_path);
 
     /* --- VS Code で開く ------------------------------------------------ */
     if cfg.open_output {
@@ -78,13 +62,32 @@ pub fn gather_files(opts: GatherOptions) -> anyhow::Result<PathBuf> {
 }
 
 /* =======================================================================
-shared helper – outline からも再利用するため pub(crate)
+shared helper – outline / graph からも再利用するため pub(crate)
 ======================================================================= */
 
-/// gather / outline 共通の “出力ファイル名決定” ロジック
+/// `.gather` の場所 (`--config-file` 指定がなければ対象ディレクトリ直下)
+pub(crate) fn config_path(opts: &GatherOptions) -> PathBuf {
+    opts.config_file
+        .clone()
+        .unwrap_or_else(|| opts.target_dir.join(".gather"))
+}
+
+/// `.gather` 読み込み → CLI 反映 までを済ませた実効設定。
+/// `.gitignore` は `exclude_patterns` に混ぜず、走査時に `GitIgnore` で判定する。
+/// `.gather` が無ければ既定値から組み立てる (テンプレ生成は gather モードのみ)。
+pub(crate) fn load_effective_config(opts: &GatherOptions) -> anyhow::Result<ConfigParams> {
+    let mut cfg = load_config_file(&config_path(opts));
+    merge_cli_into_config(opts, &mut cfg)?;
+    Ok(cfg)
+}
+
+/// gather / outline / graph 共通の “出力ファイル名決定” ロジック
+///
+/// `ext` は既定ファイル名 (`output.<ext>`) の拡張子。`--output` 指定時は無視する。
 pub(crate) fn determine_output_path(
     opts: &GatherOptions,
     cfg: &ConfigParams,
+    ext: &str,
 ) -> anyhow::Result<PathBuf> {
     if let Some(ref p) = opts.output_file {
         return Ok(p.clone());
@@ -96,9 +99,9 @@ pub(crate) fn determine_output_path(
     }
 
     let fname = if cfg.use_timestamp {
-        format!("output_{}.txt", Local::now().format("%Y%m%d%H%M%S"))
+        format!("output_{}.{ext}", Local::now().format("%Y%m%d%H%M%S"))
     } else {
-        "output.txt".into()
+        format!("output.{ext}")
     };
     Ok(dir.join(fname))
 }
@@ -141,25 +144,84 @@ fn create_gather_template(opts: &GatherOptions, path: &Path) -> anyhow::Result<(
 }
 
 fn merge_cli_into_config(opts: &GatherOptions, cfg: &mut ConfigParams) -> anyhow::Result<()> {
+    // 値を上書きし、出どころを CLI として記録する
+    macro_rules! cli {
+        ($field:ident = $value:expr) => {{
+            cfg.$field = $value;
+            cfg.sources.insert(stringify!($field).into(), Source::Cli);
+        }};
+    }
+    let extend =
+        |list: &mut Vec<String>, name: &str, sources: &mut BTreeMap<_, _>, pats: &[String]| {
+            for p in pats {
+                sources.insert(format!("{name}[{}]", list.len()), Source::Cli);
+                list.push(p.clone());
+            }
+        };
+
     if let Some(n) = opts.max_lines {
-        cfg.max_lines = n;
+        cli!(max_lines = n);
     }
     if let Some(b) = opts.max_file_size {
-        cfg.max_file_size = Some(b);
+        cli!(max_file_size = Some(b));
+    }
+    extend(
+        &mut cfg.exclude_patterns,
+        "exclude_patterns",
+        &mut cfg.sources,
+        &opts.extra_exclude_patterns,
+    );
+    extend(
+        &mut cfg.skip_content_patterns,
+        "skip_content_patterns",
+        &mut cfg.sources,
+        &opts.extra_skip_patterns,
+    );
+    extend(
+        &mut cfg.include_patterns,
+        "include_patterns",
+        &mut cfg.sources,
+        &opts.include_patterns,
+    );
+    if let Some(d) = opts.outline_detail {
+        cli!(outline_detail = d);
+    }
+    if let Some(l) = opts.outline_layout {
+        cli!(outline_layout = l);
+    }
+    if let Some(d) = opts.outline_docs {
+        cli!(outline_docs = d);
+    }
+    if let Some(v) = &opts.token_vocab {
+        // CLI の相対パスはカレントディレクトリ基準
+        cli!(token_vocab = Some(std::path::absolute(v).unwrap_or_else(|_| v.clone())));
+    }
+    if opts.tree_tokens {
+        cli!(tree_tokens = true);
+    }
+    if opts.line_numbers {
+        cli!(line_numbers = true);
+    }
+    if let Some(t) = opts.truncate {
+        cli!(truncate = t);
+    }
+    if let Some(n) = opts.max_tokens {
+        cli!(max_tokens = Some(n));
+    }
+    if let Some(s) = opts.split {
+        cli!(split = Some(s));
+    }
+    if let Some(f) = opts.output_format {
+        cli!(output_format = f);
     }
-    if !opts.extra_exclude_patterns.is_empty() {
-        cfg.exclude_patterns
-            .extend(opts.extra_exclude_patterns.clone());
+    if opts.use_timestamp {
+        cli!(use_timestamp = true);
     }
-    if !opts.extra_skip_patterns.is_empty() {
-        cfg.skip_content_patterns
-            .extend(opts.extra_skip_patterns.clone());
+    if opts.no_open {
+        cli!(open_output = false);
     }
-    if !opts.include_patterns.is_empty() {
-        cfg.include_patterns.extend(opts.include_patterns.clone());
+    if opts.use_gitignore {
+        cli!(use_gitignore = true);
     }
-    cfg.use_timestamp |= opts.use_timestamp;
-    cfg.open_output &= !opts.no_open;
-    cfg.use_gitignore |= opts.use_gitignore;
     Ok(())
 }
@@ -1,100 +1,192 @@
 // src/gitignore.rs
+//
+// .gitignore の判定 (git と同じ意味論)
+//
+// - 走査中の各ディレクトリの `.gitignore` (深い階層ほど優先)
+// - `!` による否定、先頭 `/` による固定、末尾 `/` によるディレクトリ限定
+// - `.git/info/exclude` と `core.excludesFile` (グローバル)
+//
+// パターンの解釈は `ignore` クレートの `Gitignore` に任せ、
+// ここではどのファイルをどの順で参照するかだけを決める。
+
+use ignore::gitignore::Gitignore;
+use ignore::Match;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// 走査中に `.gitignore` を適用する判定器
+pub struct GitIgnore {
+    /// 対象ディレクトリを含む git リポジトリのルート (無ければ対象ディレクトリ)
+    top: PathBuf,
+    /// ディレクトリごとの `.gitignore` (必要になった時点で読み込む)
+    dirs: RefCell<HashMap<PathBuf, Option<Gitignore>>>,
+    /// `.git/info/exclude` → `core.excludesFile` の順に参照する
+    fallback: Vec<Gitignore>,
+}
 
-use std::fs;
-use std::io::{self, BufRead};
-use std::path::Path;
-
-/// .gitignoreファイルを解析して除外パターンのリストを返す
-pub fn parse_gitignore(path: &Path) -> io::Result<Vec<String>> {
-    let file = fs::File::open(path)?;
-    let reader = io::BufReader::new(file);
-    let mut patterns = Vec::new();
-
-    for line in reader.lines() {
-        let line = line?;
-        let trimmed = line.trim();
-
-        // 空行やコメントをスキップ
-        if trimmed.is_empty() || trimmed.starts_with('#') {
-            continue;
+impl GitIgnore {
+    pub fn new(target_dir: &Path) -> Self {
+        let target = std::path::absolute(target_dir).unwrap_or_else(|_| target_dir.into());
+        let repo = target.ancestors().find(|d| d.join(".git").exists());
+        let top = repo.unwrap_or(&target).to_path_buf();
+
+        let mut fallback = Vec::new();
+        if let Some(repo) = repo {
+            let exclude = repo.join(".git/info/exclude");
+            if exclude.is_file() {
+                let (gi, err) = Gitignore::new(&exclude);
+                warn(&exclude, err);
+                fallback.push(gi);
+            }
         }
+        let (global, err) = Gitignore::global();
+        if let Some(e) = err {
+            eprintln!("core.excludesFile を読み込めません: {e}");
+        }
+        fallback.push(global);
 
-        // パターンを標準化
-        let pattern = normalize_pattern(trimmed);
-        patterns.push(pattern);
+        Self {
+            top,
+            dirs: RefCell::new(HashMap::new()),
+            fallback,
+        }
     }
 
-    Ok(patterns)
+    /// `path` (絶対パスでも相対パスでも可) が無視対象か
+    ///
+    /// 親ディレクトリが無視されているかは見ない (走査側で枝刈りする前提)。
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        let path = std::path::absolute(path).unwrap_or_else(|_| path.into());
+        for dir in path.ancestors().skip(1) {
+            if !dir.starts_with(&self.top) {
+                break;
+            }
+            let mut dirs = self.dirs.borrow_mut();
+            let gi = dirs.entry(dir.to_path_buf()).or_insert_with(|| load(dir));
+            if let Some(verdict) = decide(gi.as_ref(), &path, is_dir) {
+                return verdict;
+            }
+        }
+        self.fallback
+            .iter()
+            .find_map(|gi| decide(Some(gi), &path, is_dir))
+            .unwrap_or(false)
+    }
 }
 
-/// gitignoreパターンを[exclude]セクション用に標準化
-fn normalize_pattern(pattern: &str) -> String {
-    let mut normalized = pattern.to_string();
+/// `target_dir` の走査で参照しうる無視ファイルの一覧 (`gather config` 用)
+///
+/// 対象ディレクトリ配下と、リポジトリのルートまでの各 `.gitignore`、
+/// `.git/info/exclude`、`core.excludesFile` のうち存在するもの。
+pub fn ignore_files(target_dir: &Path) -> Vec<PathBuf> {
+    let target = std::path::absolute(target_dir).unwrap_or_else(|_| target_dir.into());
+    let repo = target.ancestors().find(|d| d.join(".git").exists());
+
+    let mut files: Vec<PathBuf> = target
+        .ancestors()
+        .skip(1)
+        .take_while(|d| repo.is_some_and(|r| d.starts_with(r)))
+        .map(|d| d.join(".gitignore"))
+        .collect();
+    files.reverse();
+    files.extend(
+        WalkDir::new(&target)
+            .into_iter()
+            .filter_entry(|e| e.file_name() != ".git")
+            .flatten()
+            .filter(|e| e.file_name() == ".gitignore")
+            .map(|e| e.into_path()),
+    );
+    files.extend(repo.map(|r| r.join(".git/info/exclude")));
+    files.extend(ignore::gitignore::gitconfig_excludes_path());
+    files.retain(|f| f.is_file());
+    files
+}
 
-    // 先頭の'/'を除去（相対パスに変換）
-    if normalized.starts_with('/') {
-        normalized = normalized[1..].to_string();
+/// `dir/.gitignore` を読み込む (無ければ `None`)
+fn load(dir: &Path) -> Option<Gitignore> {
+    let file = dir.join(".gitignore");
+    if !file.is_file() {
+        return None;
     }
+    let (gi, err) = Gitignore::new(&file);
+    warn(&file, err);
+    Some(gi)
+}
 
-    // 末尾の'/'はそのまま（ディレクトリ判定に使用）
-    // '**'パターンもそのまま（globsetが対応）
-
-    // '!'（否定）で始まるパターンは現時点ではサポートしない
-    if normalized.starts_with('!') {
-        return String::new();
+/// 一致すれば `Some(無視するか)`、一致しなければ `None`
+fn decide(gi: Option<&Gitignore>, path: &Path, is_dir: bool) -> Option<bool> {
+    match gi?.matched(path, is_dir) {
+        Match::None => None,
+        Match::Ignore(_) => Some(true),
+        Match::Whitelist(_) => Some(false),
     }
+}
 
-    normalized
+fn warn(file: &Path, err: Option<ignore::Error>) {
+    if let Some(e) = err {
+        eprintln!("{} の一部を読み込めません: {e}", file.display());
+    }
 }
 
+/* --------------------------------------------------------------------- */
+/* tests                                                                 */
+/* --------------------------------------------------------------------- */
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::fs::File;
-    use std::io::Write;
+    use std::fs;
     use tempfile::tempdir;
 
     #[test]
-    fn test_normalize_pattern() {
-        assert_eq!(normalize_pattern("/node_modules"), "node_modules");
-        assert_eq!(normalize_pattern("*.log"), "*.log");
-        assert_eq!(normalize_pattern("dist/"), "dist/");
-        assert_eq!(normalize_pattern("**/*.tmp"), "**/*.tmp");
-        assert_eq!(normalize_pattern("!temp"), ""); // 否定パターンは空文字列に
+    fn negation_anchoring_and_dir_only() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join(".git")).unwrap();
+        fs::write(
+            root.join(".gitignore"),
+            "*.log\n!keep.log\n/build\ncache/\n",
+        )
+        .unwrap();
+        let gi = GitIgnore::new(root);
+
+        assert!(gi.is_ignored(&root.join("a.log"), false));
+        assert!(!gi.is_ignored(&root.join("keep.log"), false), "negation");
+        assert!(gi.is_ignored(&root.join("build"), true));
+        assert!(
+            !gi.is_ignored(&root.join("src/build"), true),
+            "anchored to root"
+        );
+        assert!(gi.is_ignored(&root.join("src/cache"), true));
+        assert!(!gi.is_ignored(&root.join("src/cache"), false), "dir-only");
     }
 
     #[test]
-    fn test_parse_gitignore() -> io::Result<()> {
-        let dir = tempdir()?;
-        let file_path = dir.path().join(".gitignore");
-        let mut file = File::create(&file_path)?;
-
-        writeln!(file, "# Node.js")?;
-        writeln!(file, "/node_modules")?;
-        writeln!(file, "*.log")?;
-        writeln!(file)?;
-        writeln!(file, "dist/")?;
-        writeln!(file, "# 否定パターン")?;
-        writeln!(file, "!keep.log")?;
-
-        let patterns = parse_gitignore(&file_path)?;
-
-        // パターンを表示して確認
-        println!("Detected patterns: {:?}", patterns);
-
-        // 期待されるパターン
-        let expected: Vec<String> = vec![
-            "node_modules".to_string(),
-            "*.log".to_string(),
-            "dist/".to_string(),
-        ];
-
-        // 空でないパターンのみをフィルタリング
-        let filtered_patterns: Vec<String> =
-            patterns.into_iter().filter(|p| !p.is_empty()).collect();
-
-        assert_eq!(filtered_patterns, expected);
-
-        Ok(())
+    fn nested_gitignore_and_info_exclude() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join(".git/info")).unwrap();
+        fs::create_dir_all(root.join("sub")).unwrap();
+        fs::write(root.join(".git/info/exclude"), "*.tmp\n").unwrap();
+        fs::write(root.join(".gitignore"), "*.gen\n").unwrap();
+        fs::write(root.join("sub/.gitignore"), "!*.gen\nlocal.txt\n").unwrap();
+        let gi = GitIgnore::new(root);
+
+        assert!(gi.is_ignored(&root.join("a.gen"), false));
+        assert!(
+            !gi.is_ignored(&root.join("sub/a.gen"), false),
+            "deeper wins"
+        );
+        assert!(gi.is_ignored(&root.join("sub/local.txt"), false));
+        assert!(
+            !gi.is_ignored(&root.join("local.txt"), false),
+            "scoped to sub/"
+        );
+        assert!(
+            gi.is_ignored(&root.join("sub/x.tmp"), false),
+            "info/exclude"
+        );
     }
 }
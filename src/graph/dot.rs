@@ -0,0 +1,63 @@
+//! src/graph/dot.rs
+//!
+//! Graphviz DOT 出力。`mod` は実線、`use` は破線で描く。
+
+use super::{EdgeKind, Graph};
+use std::io::Write;
+
+pub fn write<W: Write>(out: &mut W, graph: &Graph) -> std::io::Result<()> {
+    writeln!(out, "digraph dependencies {{")?;
+    writeln!(out, "    rankdir=LR;")?;
+    writeln!(out, "    node [shape=box, fontname=\"monospace\"];")?;
+    writeln!(out)?;
+    for n in &graph.nodes {
+        writeln!(out, "    {};", quote(&n.path))?;
+    }
+    writeln!(out)?;
+    for e in &graph.edges {
+        let style = match e.kind {
+            EdgeKind::Mod => "solid",
+            EdgeKind::Use => "dashed",
+        };
+        writeln!(
+            out,
+            "    {} -> {} [style={style}];",
+            quote(&e.from),
+            quote(&e.to)
+        )?;
+    }
+    writeln!(out, "}}")
+}
+
+/// DOT の ID として二重引用符で囲む
+fn quote(id: &str) -> String {
+    format!("\"{}\"", id.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/* ------------------------------------------------------------------ */
+/* tests                                                              */
+/* ------------------------------------------------------------------ */
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::sample_graph;
+
+    #[test]
+    fn renders_nodes_and_styled_edges() {
+        let g = sample_graph();
+
+        let mut buf = Vec::new();
+        write(&mut buf, &g).unwrap();
+        let dot = String::from_utf8(buf).unwrap();
+        assert!(dot.starts_with("digraph dependencies {"));
+        assert!(dot.contains("    \"src/a.rs\";\n    \"src/lib.rs\";\n"));
+        assert!(dot.contains("\"src/lib.rs\" -> \"src/a.rs\" [style=solid];"));
+        assert!(dot.contains("\"src/a.rs\" -> \"src/lib.rs\" [style=dashed];"));
+        assert!(dot.trim_end().ends_with('}'));
+    }
+
+    #[test]
+    fn quotes_are_escaped() {
+        assert_eq!(quote(r#"a"b\c"#), r#""a\"b\\c""#);
+    }
+}
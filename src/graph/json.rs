@@ -0,0 +1,84 @@
+//! src/graph/json.rs
+//!
+//! JSON 出力 (隣接リスト): `{"nodes":[{"path","language","edges":[{"to","kind"}]}]}`
+
+use super::{EdgeKind, Graph};
+use serde::Serialize;
+use std::io::Write;
+
+#[derive(Serialize)]
+struct AdjacencyList<'a> {
+    nodes: Vec<NodeOut<'a>>,
+}
+
+/// ノードと、そこから出る辺
+#[derive(Serialize)]
+struct NodeOut<'a> {
+    path: &'a str,
+    language: &'a str,
+    edges: Vec<EdgeOut<'a>>,
+}
+
+#[derive(Serialize)]
+struct EdgeOut<'a> {
+    to: &'a str,
+    kind: EdgeKind,
+}
+
+pub fn write<W: Write>(out: &mut W, graph: &Graph) -> std::io::Result<()> {
+    let nodes = graph
+        .nodes
+        .iter()
+        .map(|n| NodeOut {
+            path: &n.path,
+            language: n.language,
+            edges: graph
+                .edges
+                .iter()
+                .filter(|e| e.from == n.path)
+                .map(|e| EdgeOut {
+                    to: &e.to,
+                    kind: e.kind,
+                })
+                .collect(),
+        })
+        .collect();
+    serde_json::to_writer_pretty(&mut *out, &AdjacencyList { nodes })?;
+    writeln!(out)
+}
+
+/* ------------------------------------------------------------------ */
+/* tests                                                              */
+/* ------------------------------------------------------------------ */
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::sample_graph;
+    use serde_json::{json, Value};
+
+    #[test]
+    fn edges_are_listed_under_their_source_node() {
+        let g = sample_graph();
+
+        let mut buf = Vec::new();
+        write(&mut buf, &g).unwrap();
+        let v: Value = serde_json::from_slice(&buf).unwrap();
+        assert_eq!(
+            v,
+            json!({
+                "nodes": [
+                    {
+                        "path": "src/a.rs",
+                        "language": "rust",
+                        "edges": [{"to": "src/lib.rs", "kind": "use"}],
+                    },
+                    {
+                        "path": "src/lib.rs",
+                        "language": "rust",
+                        "edges": [{"to": "src/a.rs", "kind": "mod"}],
+                    },
+                ],
+            })
+        );
+    }
+}
@@ -0,0 +1,62 @@
+//! src/graph/mermaid.rs
+//!
+//! Mermaid `flowchart` 出力 (GitHub などの Markdown ビューアでそのまま描画される)。
+//! `mod` は実線 `-->`、`use` は点線 `-.->` で描く。
+
+use super::{EdgeKind, Graph};
+use std::collections::HashMap;
+use std::io::Write;
+
+pub fn write<W: Write>(out: &mut W, graph: &Graph) -> std::io::Result<()> {
+    // パスは Mermaid の ID に使えないので連番 ID を振り、ラベルに元のパスを出す
+    let ids: HashMap<&str, String> = graph
+        .nodes
+        .iter()
+        .enumerate()
+        .map(|(i, n)| (n.path.as_str(), format!("n{i}")))
+        .collect();
+
+    writeln!(out, "```mermaid")?;
+    writeln!(out, "flowchart LR")?;
+    for n in &graph.nodes {
+        writeln!(out, "    {}[\"{}\"]", ids[n.path.as_str()], escape(&n.path))?;
+    }
+    for e in &graph.edges {
+        let (Some(from), Some(to)) = (ids.get(e.from.as_str()), ids.get(e.to.as_str())) else {
+            continue;
+        };
+        let arrow = match e.kind {
+            EdgeKind::Mod => "-->",
+            EdgeKind::Use => "-.->",
+        };
+        writeln!(out, "    {from} {arrow} {to}")?;
+    }
+    writeln!(out, "```")
+}
+
+/// ラベル内の `"` は HTML エンティティで表す
+fn escape(label: &str) -> String {
+    label.replace('"', "#quot;")
+}
+
+/* ------------------------------------------------------------------ */
+/* tests                                                              */
+/* ------------------------------------------------------------------ */
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::sample_graph;
+
+    #[test]
+    fn renders_flowchart_block() {
+        let g = sample_graph();
+
+        let mut buf = Vec::new();
+        write(&mut buf, &g).unwrap();
+        assert_eq!(
+            String::from_utf8(buf).unwrap(),
+            "```mermaid\nflowchart LR\n    n0[\"src/a.rs\"]\n    n1[\"src/lib.rs\"]\n    \
+             n0 -.-> n1\n    n1 --> n0\n```\n"
+        );
+    }
+}
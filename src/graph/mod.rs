@@ -0,0 +1,133 @@
+//! src/graph/mod.rs
+//!
+//! ファイル単位の依存グラフ (graph モード)
+//!
+//! ```text
+//!  collect_entries (exclude 反映) → 言語ごとの Resolver → Graph → dot / mermaid / json
+//! ```
+//! 今のところ Rust (`mod` / `use`) のみ対応。
+
+mod dot;
+mod json;
+mod mermaid;
+mod rust;
+
+use crate::gitignore::GitIgnore;
+use crate::model::{ConfigParams, GraphFormat};
+use crate::scanner::scope::Scopes;
+use crate::scanner::walker::collect_entries;
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 依存の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EdgeKind {
+    /// `mod foo;` による親 → 子ファイル
+    Mod,
+    /// `use crate::foo::Bar` などによる参照
+    Use,
+}
+
+/// `from` が `to` に依存している (パスは対象ディレクトリからの相対、区切りは `/`)
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Edge {
+    pub from: String,
+    pub to: String,
+    pub kind: EdgeKind,
+}
+
+/// グラフのノード = 1 ファイル
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Node {
+    pub path: String,
+    pub language: &'static str,
+}
+
+/// ファイル単位の依存グラフ (出力順を安定させるため BTreeSet)
+#[derive(Debug, Default)]
+pub struct Graph {
+    pub nodes: BTreeSet<Node>,
+    pub edges: BTreeSet<Edge>,
+}
+
+/// 言語ごとの依存解決器
+trait Resolver {
+    /// ノードに付ける言語名
+    fn language(&self) -> &'static str;
+
+    /// このファイルを解析対象にするか
+    fn supports(&self, path: &Path) -> bool;
+
+    /// 対象ファイル群 (相対パス) から依存辺を求める
+    fn resolve(&self, root: &Path, files: &[PathBuf]) -> Vec<Edge>;
+}
+
+const RESOLVERS: &[&dyn Resolver] = &[&rust::RustResolver];
+
+/// グラフを組み立てて `output` に指定形式で書き出す
+pub fn run(dir: &Path, output: &Path, fmt: GraphFormat, cfg: &ConfigParams) -> anyhow::Result<()> {
+    let graph = build(dir, cfg);
+    let mut out = fs::File::create(output)?;
+    match fmt {
+        GraphFormat::Dot => dot::write(&mut out, &graph)?,
+        GraphFormat::Mermaid => mermaid::write(&mut out, &graph)?,
+        GraphFormat::Json => json::write(&mut out, &graph)?,
+    }
+    Ok(())
+}
+
+/// `[exclude]` / `.gitignore` を反映したうえで依存グラフを構築する
+pub fn build(dir: &Path, cfg: &ConfigParams) -> Graph {
+    let scopes = Scopes::new(dir, cfg);
+    let gitignore = cfg.use_gitignore.then(|| GitIgnore::new(dir));
+    let entries = collect_entries(dir, &scopes, gitignore.as_ref(), true);
+
+    let mut graph = Graph::default();
+    for resolver in RESOLVERS {
+        let files: Vec<PathBuf> = entries
+            .iter()
+            .map(|e| e.path().strip_prefix(dir).unwrap_or(e.path()).to_path_buf())
+            .filter(|rel| resolver.supports(rel))
+            .collect();
+        graph.nodes.extend(files.iter().map(|f| Node {
+            path: node_id(f),
+            language: resolver.language(),
+        }));
+        graph.edges.extend(resolver.resolve(dir, &files));
+    }
+    graph
+}
+
+/// 相対パス → ノード ID (OS によらず `/` 区切り)
+fn node_id(rel: &Path) -> String {
+    rel.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// レンダラのテスト用: `src/lib.rs` と `src/a.rs` が `mod` / `use` で相互に依存するグラフ
+#[cfg(test)]
+fn sample_graph() -> Graph {
+    let mut g = Graph::default();
+    for path in ["src/lib.rs", "src/a.rs"] {
+        g.nodes.insert(Node {
+            path: path.into(),
+            language: "rust",
+        });
+    }
+    g.edges.insert(Edge {
+        from: "src/lib.rs".into(),
+        to: "src/a.rs".into(),
+        kind: EdgeKind::Mod,
+    });
+    g.edges.insert(Edge {
+        from: "src/a.rs".into(),
+        to: "src/lib.rs".into(),
+        kind: EdgeKind::Use,
+    });
+    g
+}
@@ -0,0 +1,353 @@
+//! src/graph/rust.rs
+//!
+//! Rust の `mod` / `use` からファイル間の依存を求める (syn ベース)
+//!
+//! 1. `mod foo;` を `foo.rs` / `foo/mod.rs` (または `#[path]`) に解決し、親 → 子の辺を張る
+//! 2. どの `mod` からも参照されないファイルをクレートルートとみなし、モジュールパスを割り当てる
+//! 3. `use crate::` / `self::` / `super::` / 子モジュール名で始まるパスを、
+//!    最も深く一致するモジュールのファイルへ解決する (外部クレートは無視)
+
+use super::{node_id, Edge, EdgeKind, Resolver};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use syn::{Expr, ExprLit, Item, Lit, Meta, UseTree};
+
+pub struct RustResolver;
+
+impl Resolver for RustResolver {
+    fn language(&self) -> &'static str {
+        "rust"
+    }
+
+    fn supports(&self, path: &Path) -> bool {
+        matches!(path.extension(), Some(ext) if ext == "rs")
+    }
+
+    fn resolve(&self, root: &Path, files: &[PathBuf]) -> Vec<Edge> {
+        let parsed: Vec<Parsed> = files.iter().map(|f| parse(&root.join(f))).collect();
+        let index: HashMap<&Path, usize> = files
+            .iter()
+            .enumerate()
+            .map(|(i, f)| (f.as_path(), i))
+            .collect();
+        let mut edges = Vec::new();
+
+        /* --- 1. mod 宣言 → ファイル -------------------------------------- */
+        // file → [(ファイル内の相対モジュールパス, 子ファイル)]
+        let mut children: Vec<Vec<(Vec<String>, usize)>> = vec![vec![]; files.len()];
+        let mut claimed: HashSet<usize> = HashSet::new();
+        for (i, p) in parsed.iter().enumerate() {
+            for decl in &p.mods {
+                if let Some(t) = locate(&files[i], decl, &index).filter(|&t| t != i) {
+                    children[i].push((decl.path.clone(), t));
+                    claimed.insert(t);
+                    edges.push(edge(&files[i], &files[t], EdgeKind::Mod));
+                }
+            }
+        }
+
+        /* --- 2. クレートルートからモジュールパスを割り当て ------------------ */
+        // (クレートルート, モジュールパス) → ファイル
+        let mut modules: HashMap<(usize, Vec<String>), usize> = HashMap::new();
+        let mut file_module: HashMap<usize, (usize, Vec<String>)> = HashMap::new();
+        for krate in (0..files.len()).filter(|i| !claimed.contains(i)) {
+            let mut stack = vec![(krate, Vec::<String>::new())];
+            while let Some((f, path)) = stack.pop() {
+                if file_module.contains_key(&f) {
+                    continue; // 循環・多重 mod は最初の 1 つだけ
+                }
+                for inline in &parsed[f].inline_mods {
+                    let full = [path.clone(), inline.clone()].concat();
+                    modules.entry((krate, full)).or_insert(f);
+                }
+                for (rel, t) in &children[f] {
+                    stack.push((*t, [path.clone(), rel.clone()].concat()));
+                }
+                modules.insert((krate, path.clone()), f);
+                file_module.insert(f, (krate, path));
+            }
+        }
+
+        /* --- 3. use → ファイル ------------------------------------------ */
+        for (&f, (krate, module)) in &file_module {
+            for u in &parsed[f].uses {
+                let cur = [module.clone(), u.scope.clone()].concat();
+                if let Some(t) = resolve_use(&modules, *krate, &cur, &u.path) {
+                    if t != f {
+                        edges.push(edge(&files[f], &files[t], EdgeKind::Use));
+                    }
+                }
+            }
+        }
+        edges
+    }
+}
+
+fn edge(from: &Path, to: &Path, kind: EdgeKind) -> Edge {
+    Edge {
+        from: node_id(from),
+        to: node_id(to),
+        kind,
+    }
+}
+
+/* ---------------- 構文解析 ---------------- */
+
+/// 1 ファイルから拾った宣言
+#[derive(Default)]
+struct Parsed {
+    /// 本体なしの `mod foo;`
+    mods: Vec<ModDecl>,
+    /// `mod foo { ... }` (ファイル内の相対パス)
+    inline_mods: Vec<Vec<String>>,
+    uses: Vec<UseRef>,
+}
+
+struct ModDecl {
+    /// インラインモジュール内なら `a::b::foo` のように外側を含む
+    path: Vec<String>,
+    /// `#[path = "..."]`
+    path_attr: Option<String>,
+}
+
+struct UseRef {
+    /// `use` が書かれたインラインモジュール
+    scope: Vec<String>,
+    /// `crate::foo::Bar` → `["crate", "foo", "Bar"]`
+    path: Vec<String>,
+}
+
+/// 読めない・構文エラーのファイルは依存なしとして扱う
+fn parse(path: &Path) -> Parsed {
+    let mut parsed = Parsed::default();
+    if let Some(file) = fs::read_to_string(path)
+        .ok()
+        .and_then(|src| syn::parse_file(&src).ok())
+    {
+        collect(&file.items, &mut Vec::new(), &mut parsed);
+    }
+    parsed
+}
+
+fn collect(items: &[Item], scope: &mut Vec<String>, out: &mut Parsed) {
+    for item in items {
+        match item {
+            Item::Mod(m) => {
+                scope.push(m.ident.to_string());
+                match &m.content {
+                    None => out.mods.push(ModDecl {
+                        path: scope.clone(),
+                        path_attr: path_attr(&m.attrs),
+                    }),
+                    Some((_, inner)) => {
+                        out.inline_mods.push(scope.clone());
+                        collect(inner, scope, out);
+                    }
+                }
+                scope.pop();
+            }
+            // `use ::foo` は常に外部クレート
+            Item::Use(u) if u.leading_colon.is_none() => {
+                let mut paths = Vec::new();
+                flatten_use(&u.tree, &mut Vec::new(), &mut paths);
+                out.uses.extend(paths.into_iter().map(|path| UseRef {
+                    scope: scope.clone(),
+                    path,
+                }));
+            }
+            _ => {}
+        }
+    }
+}
+
+fn path_attr(attrs: &[syn::Attribute]) -> Option<String> {
+    attrs
+        .iter()
+        .find(|a| a.path().is_ident("path"))
+        .and_then(|a| match &a.meta {
+            Meta::NameValue(nv) => match &nv.value {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(s), ..
+                }) => Some(s.value()),
+                _ => None,
+            },
+            _ => None,
+        })
+}
+
+/// `use a::{b, c::*}` → `[a, b]`, `[a, c]`
+fn flatten_use(tree: &UseTree, prefix: &mut Vec<String>, out: &mut Vec<Vec<String>>) {
+    match tree {
+        UseTree::Path(p) => {
+            prefix.push(p.ident.to_string());
+            flatten_use(&p.tree, prefix, out);
+            prefix.pop();
+        }
+        UseTree::Name(n) if n.ident == "self" => out.push(prefix.clone()),
+        UseTree::Name(n) => out.push([prefix.clone(), vec![n.ident.to_string()]].concat()),
+        UseTree::Rename(r) => out.push([prefix.clone(), vec![r.ident.to_string()]].concat()),
+        UseTree::Glob(_) => out.push(prefix.clone()),
+        UseTree::Group(g) => g.items.iter().for_each(|t| flatten_use(t, prefix, out)),
+    }
+}
+
+/* ---------------- 解決 ---------------- */
+
+/// `mod` 宣言の実ファイルを探す (走査対象に無ければ None)
+fn locate(file: &Path, decl: &ModDecl, index: &HashMap<&Path, usize>) -> Option<usize> {
+    let parent = file.parent().unwrap_or(Path::new(""));
+    if let (Some(attr), 1) = (&decl.path_attr, decl.path.len()) {
+        return index.get(normalize(&parent.join(attr)).as_path()).copied();
+    }
+
+    // mod.rs / lib.rs / main.rs は自分のディレクトリ、それ以外は `<stem>/` が基点。
+    // tests/*.rs や src/bin/*.rs のようなクレートルート用に親ディレクトリも試す。
+    let owns_dir = matches!(
+        file.file_name().and_then(|n| n.to_str()),
+        Some("mod.rs" | "lib.rs" | "main.rs")
+    );
+    let mut bases = vec![];
+    if !owns_dir {
+        bases.push(parent.join(file.file_stem().unwrap_or_default()));
+    }
+    bases.push(parent.to_path_buf());
+
+    let (name, outer) = decl.path.split_last()?;
+    bases.iter().find_map(|base| {
+        let dir: PathBuf = outer.iter().fold(base.clone(), |d, m| d.join(m));
+        [
+            dir.join(format!("{name}.rs")),
+            dir.join(name).join("mod.rs"),
+        ]
+        .iter()
+        .find_map(|c| index.get(normalize(c).as_path()).copied())
+    })
+}
+
+/// `./` と `..` を字句的に畳む
+fn normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for c in path.components() {
+        match c {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                out.pop();
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+/// `use` パスを最も深く一致するモジュールのファイルへ
+fn resolve_use(
+    modules: &HashMap<(usize, Vec<String>), usize>,
+    krate: usize,
+    cur: &[String],
+    segs: &[String],
+) -> Option<usize> {
+    let (mut path, rest): (Vec<String>, &[String]) = match segs.first()?.as_str() {
+        "crate" => (vec![], &segs[1..]),
+        "self" => (cur.to_vec(), &segs[1..]),
+        "super" => {
+            let mut base = cur.to_vec();
+            let supers = segs.iter().take_while(|s| *s == "super").count();
+            for _ in 0..supers {
+                base.pop()?;
+            }
+            (base, &segs[supers..])
+        }
+        // 子モジュール名で始まる相対パス (2018 edition)。それ以外は外部クレート
+        first => {
+            let child = [cur.to_vec(), vec![first.to_string()]].concat();
+            if !modules.contains_key(&(krate, child)) {
+                return None;
+            }
+            (cur.to_vec(), segs)
+        }
+    };
+
+    let mut target = *modules.get(&(krate, path.clone()))?;
+    for seg in rest {
+        path.push(seg.clone());
+        match modules.get(&(krate, path.clone())) {
+            Some(&t) => target = t,
+            None => break,
+        }
+    }
+    Some(target)
+}
+
+/* ------------------------------------------------------------------ */
+/* tests                                                              */
+/* ------------------------------------------------------------------ */
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn edges(files: &[(&str, &str)]) -> Vec<(String, String, EdgeKind)> {
+        let dir = tempdir().unwrap();
+        let mut rels = Vec::new();
+        for (rel, src) in files {
+            let p = dir.path().join(rel);
+            fs::create_dir_all(p.parent().unwrap()).unwrap();
+            fs::write(&p, src).unwrap();
+            rels.push(PathBuf::from(rel));
+        }
+        let mut out: Vec<_> = RustResolver
+            .resolve(dir.path(), &rels)
+            .into_iter()
+            .map(|e| (e.from, e.to, e.kind))
+            .collect();
+        out.sort();
+        out.dedup();
+        out
+    }
+
+    fn e(from: &str, to: &str, kind: EdgeKind) -> (String, String, EdgeKind) {
+        (from.into(), to.into(), kind)
+    }
+
+    #[test]
+    fn resolves_mod_and_use_across_files() {
+        use EdgeKind::*;
+        let got = edges(&[
+            ("src/lib.rs", "mod a; pub mod b; mod c { mod d; }"),
+            (
+                "src/a.rs",
+                "use crate::b::Thing; use std::fs; mod inner; use inner::X;",
+            ),
+            ("src/a/inner.rs", "use super::super::b; use self::Y;"),
+            ("src/b.rs", "pub struct Thing;"),
+            ("src/c/d.rs", "use crate::{a, b::*};"),
+            ("tests/it.rs", "mod common; use gather_files_lib::run;"),
+            ("tests/common/mod.rs", ""),
+        ]);
+        assert_eq!(
+            got,
+            vec![
+                e("src/a.rs", "src/a/inner.rs", Mod),
+                e("src/a.rs", "src/a/inner.rs", Use),
+                e("src/a.rs", "src/b.rs", Use),
+                e("src/a/inner.rs", "src/b.rs", Use),
+                e("src/c/d.rs", "src/a.rs", Use),
+                e("src/c/d.rs", "src/b.rs", Use),
+                e("src/lib.rs", "src/a.rs", Mod),
+                e("src/lib.rs", "src/b.rs", Mod),
+                e("src/lib.rs", "src/c/d.rs", Mod),
+                e("tests/it.rs", "tests/common/mod.rs", Mod),
+            ]
+        );
+    }
+
+    #[test]
+    fn path_attribute_is_honored() {
+        let got = edges(&[
+            ("src/main.rs", "#[path = \"../gen/out.rs\"] mod generated;"),
+            ("gen/out.rs", ""),
+        ]);
+        assert_eq!(got, vec![e("src/main.rs", "gen/out.rs", EdgeKind::Mod)]);
+    }
+}
@@ -0,0 +1,186 @@
+//! src/inspect.rs
+//!
+//! `gather config` – 実効設定 (`.gather` → CLI 反映後) と各値の出どころを表示する。
+//!
+//! ```text
+//! max_lines        = 50           # CLI
+//! exclude_patterns = [
+//!   "gather/",                    # .gather line 27
+//! ]
+//! ```
+
+use crate::gather::{config_path, load_effective_config};
+use crate::gitignore::ignore_files;
+use crate::model::{CLIOptions as GatherOptions, ConfigFormat, ConfigParams, Source};
+
+use serde_json::{json, Value};
+use std::io::Write;
+
+/// 1 つの設定値 (リストは要素ごとに出どころを持つ)
+enum Entry {
+    Scalar(Value, Source),
+    List(Vec<(Value, Source)>),
+}
+
+/// 実効設定を `out` に書き出す
+pub fn run(opts: &GatherOptions, fmt: ConfigFormat, out: &mut dyn Write) -> anyhow::Result<()> {
+    let cfg = load_effective_config(opts)?;
+    let entries = entries(&cfg)?;
+    let gitignore: Vec<String> = if cfg.use_gitignore {
+        ignore_files(&opts.target_dir)
+            .iter()
+            .map(|p| p.display().to_string())
+            .collect()
+    } else {
+        vec![]
+    };
+    let config_file = config_path(opts);
+    let config_file = config_file
+        .is_file()
+        .then(|| config_file.display().to_string());
+
+    match fmt {
+        ConfigFormat::Text => {
+            match &config_file {
+                Some(p) => writeln!(out, "# .gather: {p}")?,
+                None => writeln!(out, "# .gather: (なし – 既定値)")?,
+            }
+            write_text(out, &entries, &gitignore)?;
+        }
+        ConfigFormat::Json => {
+            let values: serde_json::Map<String, Value> = entries
+                .into_iter()
+                .map(|(key, entry)| {
+                    let v = match entry {
+                        Entry::Scalar(value, source) => json!({"value": value, "source": source}),
+                        Entry::List(items) => items
+                            .into_iter()
+                            .map(|(value, source)| json!({"value": value, "source": source}))
+                            .collect(),
+                    };
+                    (key, v)
+                })
+                .collect();
+            let gitignore: Vec<Value> = gitignore
+                .iter()
+                .map(|p| json!({"path": p, "source": Source::Gitignore}))
+                .collect();
+            let doc = json!({
+                "config_file": config_file,
+                "values": values,
+                "gitignore": gitignore,
+            });
+            serde_json::to_writer_pretty(&mut *out, &doc)?;
+            writeln!(out)?;
+        }
+    }
+    Ok(())
+}
+
+/// `ConfigParams` の各フィールドと出どころ (キー順)
+fn entries(cfg: &ConfigParams) -> anyhow::Result<Vec<(String, Entry)>> {
+    let source = |key: &str| cfg.sources.get(key).copied().unwrap_or(Source::Default);
+    let Value::Object(fields) = serde_json::to_value(cfg)? else {
+        anyhow::bail!("ConfigParams must serialize to an object");
+    };
+    Ok(fields
+        .into_iter()
+        .map(|(key, value)| {
+            let entry = match value {
+                Value::Array(items) => Entry::List(
+                    items
+                        .into_iter()
+                        .enumerate()
+                        .map(|(i, v)| (v, source(&format!("{key}[{i}]"))))
+                        .collect(),
+                ),
+                value => Entry::Scalar(value, source(&key)),
+            };
+            (key, entry)
+        })
+        .collect())
+}
+
+/// `key = value  # 出どころ` の形で、コメント位置を揃えて書く
+fn write_text(
+    out: &mut dyn Write,
+    entries: &[(String, Entry)],
+    gitignore: &[String],
+) -> std::io::Result<()> {
+    let mut lines: Vec<(String, Option<Source>)> = Vec::new();
+    let key_width = entries.iter().map(|(k, _)| k.len()).max().unwrap_or(0);
+    for (key, entry) in entries {
+        match entry {
+            Entry::Scalar(value, source) => {
+                lines.push((format!("{key:<key_width$} = {value}"), Some(*source)));
+            }
+            Entry::List(items) if items.is_empty() => {
+                lines.push((format!("{key:<key_width$} = []"), None));
+            }
+            Entry::List(items) => {
+                lines.push((format!("{key:<key_width$} = ["), None));
+                for (value, source) in items {
+                    lines.push((format!("  {value},"), Some(*source)));
+                }
+                lines.push(("]".into(), None));
+            }
+        }
+    }
+    if !gitignore.is_empty() {
+        lines.push((String::new(), None));
+        lines.push(("# use_gitignore: 走査時に参照するファイル".into(), None));
+        for path in gitignore {
+            lines.push((path.clone(), Some(Source::Gitignore)));
+        }
+    }
+
+    let width = lines
+        .iter()
+        .filter(|(_, s)| s.is_some())
+        .map(|(l, _)| l.chars().count())
+        .max()
+        .unwrap_or(0);
+    for (line, source) in lines {
+        match source {
+            Some(s) => writeln!(out, "{line:<width$}  # {s}")?,
+            None => writeln!(out, "{line}")?,
+        }
+    }
+    Ok(())
+}
+
+/* --------------------------------------------------------------------- */
+/* tests                                                                 */
+/* --------------------------------------------------------------------- */
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn list_items_carry_their_own_source() {
+        let mut cfg = ConfigParams {
+            max_lines: 50,
+            exclude_patterns: vec!["gather/".into(), "target/".into()],
+            ..ConfigParams::default()
+        };
+        cfg.sources.insert("max_lines".into(), Source::Cli);
+        cfg.sources
+            .insert("exclude_patterns[0]".into(), Source::Gather { line: 3 });
+        cfg.sources
+            .insert("exclude_patterns[1]".into(), Source::Cli);
+
+        let mut out = Vec::new();
+        write_text(&mut out, &entries(&cfg).unwrap(), &[]).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let line = |needle: &str| {
+            text.lines()
+                .find(|l| l.contains(needle))
+                .unwrap_or_else(|| panic!("{needle} not in\n{text}"))
+                .to_string()
+        };
+        assert!(line("max_lines ").ends_with("# CLI"));
+        assert!(line("\"gather/\",").ends_with("# .gather line 3"));
+        assert!(line("\"target/\",").ends_with("# CLI"));
+        assert!(line("skip_binary ").ends_with("# default"));
+    }
+}
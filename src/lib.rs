@@ -3,6 +3,8 @@
 //! ```text
 //!  ・RunMode::Gather   → gather::gather_files()
 //!  ・RunMode::Outline  → outline::run() + VSCode オープン
+//!  ・RunMode::Graph    → graph::run()   + VSCode オープン (DOT / Mermaid / JSON)
+//!  ・gather config     → inspect::run()  (実効設定と各値の出どころ)
 //! ```
 //! それ以外の実装詳細は個別モジュールへ委譲し、ここを薄く保つ。
 
@@ -15,16 +17,22 @@ mod args;
 mod config;
 mod gather; // ← NEW
 mod gitignore;
+mod graph;
+mod inspect;
 mod model;
 mod outline;
 mod scanner;
+mod tokens;
 pub mod updater;
 
 /* ──────────────────── public re-exports ────────────────── */
 
-pub use crate::args::parse_args;
+pub use crate::args::{parse_args, parse_config_args};
 pub use gather::gather_files as gather; // 旧 API 継続
-pub use model::{CLIOptions as GatherOptions, ConfigParams, OutlineFormat, RunMode};
+pub use model::{
+    CLIOptions as GatherOptions, ConfigFormat, ConfigParams, GraphFormat, OutlineDetail,
+    OutlineDocs, OutlineFormat, OutlineLayout, RunMode,
+};
 
 /* ───────────────────────── deps ────────────────────────── */
 
@@ -40,20 +48,44 @@ pub fn run(opts: GatherOptions) -> anyhow::Result<PathBuf> {
     match opts.mode {
         RunMode::Gather => gather::gather_files(opts),
         RunMode::Outline(fmt) => run_outline(opts, fmt),
+        RunMode::Graph(fmt) => run_graph(opts, fmt),
     }
 }
 
+/// `gather config` – `.gather` → CLI 反映後の実効設定を標準出力へ表示する。
+pub fn show_config(opts: &GatherOptions, fmt: ConfigFormat) -> anyhow::Result<()> {
+    inspect::run(opts, fmt, &mut std::io::stdout().lock())
+}
+
 /* -----------------------------------------------------------------
    outline wrapper
 ----------------------------------------------------------------- */
 
 fn run_outline(opts: GatherOptions, fmt: OutlineFormat) -> anyhow::Result<PathBuf> {
-    // gather と同じ出力パス決定ロジックを再利用
-    let output = gather::determine_output_path(&opts, &ConfigParams::default())?;
+    // gather と同じ設定読み込み・出力パス決定ロジックを再利用
+    let cfg = gather::load_effective_config(&opts)?;
+    let output = gather::determine_output_path(&opts, &cfg, "txt")?;
+
+    outline::run(&opts.target_dir, &output, fmt, &cfg).context("outline failed")?;
+
+    if cfg.open_output {
+        let _ = Command::new("code").arg(&output).status();
+    }
+    Ok(output)
+}
+
+/* -----------------------------------------------------------------
+   graph wrapper
+----------------------------------------------------------------- */
+
+fn run_graph(opts: GatherOptions, fmt: GraphFormat) -> anyhow::Result<PathBuf> {
+    // `.gather` の exclude / .gitignore を gather と同じく反映
+    let cfg = gather::load_effective_config(&opts)?;
+    let output = gather::determine_output_path(&opts, &cfg, fmt.extension())?;
 
-    outline::run(&opts.target_dir, &output, fmt).context("outline failed")?;
+    graph::run(&opts.target_dir, &output, fmt, &cfg).context("graph failed")?;
 
-    if !opts.no_open {
+    if cfg.open_output {
         let _ = Command::new("code").arg(&output).status();
     }
     Ok(output)
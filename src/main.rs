@@ -9,6 +9,14 @@ fn main() -> anyhow::Result<()> {
             }
             return Ok(());
         }
+        if cmd == "config" {
+            let (opts, fmt) = lib::parse_config_args();
+            if let Err(e) = lib::show_config(&opts, fmt) {
+                eprintln!("Error: {e:?}");
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
     }
 
     let cli_opts = lib::parse_args();
@@ -1,5 +1,7 @@
 #![allow(missing_docs)]
 
+use serde::Serialize;
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 /* ---------- CLI -> Core options ---------- */
@@ -8,6 +10,7 @@ use std::path::PathBuf;
 pub enum RunMode {
     Gather,
     Outline(OutlineFormat),
+    Graph(GraphFormat),
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -16,6 +19,198 @@ pub enum OutlineFormat {
     Json,
 }
 
+/// 依存グラフの出力形式
+#[derive(Debug, Clone, Copy)]
+pub enum GraphFormat {
+    /// Graphviz DOT (`output.dot`)
+    Dot,
+    /// Markdown に埋め込む Mermaid `flowchart` (`output.md`)
+    Mermaid,
+    /// ノード・辺の JSON (`output.json`)
+    Json,
+}
+
+impl GraphFormat {
+    /// 既定の出力ファイル拡張子
+    pub fn extension(self) -> &'static str {
+        match self {
+            GraphFormat::Dot => "dot",
+            GraphFormat::Mermaid => "md",
+            GraphFormat::Json => "json",
+        }
+    }
+}
+
+/// アウトラインの詳細度
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutlineDetail {
+    /// `- **fn** gather` のように種別と名前だけ
+    #[default]
+    Names,
+    /// 本体を省いたシグネチャ (引数・戻り値・フィールド・バリアントなど) も出力
+    Signatures,
+}
+
+/// アウトラインの並べ方
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutlineLayout {
+    /// モジュール・クラス・impl の入れ子を保った木 (既定)
+    #[default]
+    Tree,
+    /// 旧来どおりの平坦なリスト (名前は `foo::bar` のような修飾名)
+    Flat,
+}
+
+/// シンボルに付いた doc コメントの出し方
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutlineDocs {
+    /// 最初の段落だけ (既定)
+    #[default]
+    Summary,
+    /// doc コメント全文
+    Full,
+    /// 出力しない
+    None,
+}
+
+impl std::str::FromStr for OutlineDocs {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "summary" | "first" => Ok(Self::Summary),
+            "full" | "all" => Ok(Self::Full),
+            "none" | "no" | "off" => Ok(Self::None),
+            other => Err(format!("unknown outline docs: {other}")),
+        }
+    }
+}
+
+impl std::str::FromStr for OutlineLayout {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "tree" => Ok(Self::Tree),
+            "flat" => Ok(Self::Flat),
+            other => Err(format!("unknown outline layout: {other}")),
+        }
+    }
+}
+
+impl std::str::FromStr for OutlineDetail {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "names" | "name" => Ok(Self::Names),
+            "signatures" | "signature" => Ok(Self::Signatures),
+            other => Err(format!("unknown outline detail: {other}")),
+        }
+    }
+}
+
+/// gather モードの出力形式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputFormat {
+    /// ツリーをフェンス、各ファイルを `### path` + フェンスで出力 (既定)
+    #[default]
+    Markdown,
+    /// `<documents><document index="n">…` 形式
+    Xml,
+    /// ヘッダとファイル一覧を持つ 1 つの JSON
+    Json,
+    /// 1 行 1 オブジェクトの JSON Lines
+    Jsonl,
+}
+
+impl OutputFormat {
+    /// 既定の出力ファイル拡張子
+    pub fn extension(self) -> &'static str {
+        match self {
+            OutputFormat::Markdown => "txt",
+            OutputFormat::Xml => "xml",
+            OutputFormat::Json => "json",
+            OutputFormat::Jsonl => "jsonl",
+        }
+    }
+}
+
+impl std::str::FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "markdown" | "md" => Ok(Self::Markdown),
+            "xml" => Ok(Self::Xml),
+            "json" => Ok(Self::Json),
+            "jsonl" => Ok(Self::Jsonl),
+            other => Err(format!("unknown output format: {other}")),
+        }
+    }
+}
+
+/// 出力を分割する上限 (`--split 500kb` / `30000tokens` / `2000lines`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SplitLimit {
+    Bytes(usize),
+    Tokens(usize),
+    Lines(usize),
+}
+
+impl std::str::FromStr for SplitLimit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_lowercase().replace('_', "");
+        let digits = s.chars().take_while(|c| c.is_ascii_digit()).count();
+        let n: usize = s[..digits]
+            .parse()
+            .map_err(|_| format!("invalid split limit: {s}"))?;
+        let bytes = |unit: usize| {
+            n.checked_mul(unit)
+                .map(Self::Bytes)
+                .ok_or_else(|| format!("split limit too large: {s}"))
+        };
+        match s[digits..].trim() {
+            "" | "b" | "bytes" => Ok(Self::Bytes(n)),
+            "kb" => bytes(1_000),
+            "mb" => bytes(1_000_000),
+            "t" | "tok" | "tokens" => Ok(Self::Tokens(n)),
+            "l" | "lines" => Ok(Self::Lines(n)),
+            other => Err(format!("unknown split unit: {other}")),
+        }
+    }
+}
+
+/// 分割した各パートの先頭に置くもの
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SplitHeader {
+    /// ディレクトリツリー全体を毎回繰り返す (既定)
+    #[default]
+    Tree,
+    /// そのパートに含まれるファイルの一覧だけ
+    Index,
+}
+
+impl std::str::FromStr for SplitHeader {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "tree" => Ok(Self::Tree),
+            "index" => Ok(Self::Index),
+            other => Err(format!("unknown split header: {other}")),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct CLIOptions {
     pub mode: RunMode,
@@ -30,11 +225,56 @@ pub struct CLIOptions {
     pub use_timestamp: bool,
     pub no_open: bool,
     pub use_gitignore: bool,
+    pub outline_detail: Option<OutlineDetail>,
+    pub outline_layout: Option<OutlineLayout>,
+    pub outline_docs: Option<OutlineDocs>,
+    pub token_vocab: Option<PathBuf>,
+    pub tree_tokens: bool,
+    pub max_tokens: Option<usize>,
+    pub split: Option<SplitLimit>,
+    pub output_format: Option<OutputFormat>,
+    pub line_numbers: bool,
+    pub truncate: Option<Truncate>,
+}
+
+/// `max_lines` を超えた本文の残し方
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Truncate {
+    /// 先頭だけ残す (既定)
+    #[default]
+    Head,
+    /// 末尾だけ残す
+    Tail,
+    /// 先頭と末尾を半分ずつ残し、間を省略
+    HeadTail,
+}
+
+impl std::str::FromStr for Truncate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().replace('-', "_").as_str() {
+            "head" => Ok(Self::Head),
+            "tail" => Ok(Self::Tail),
+            "head_tail" | "head+tail" | "both" => Ok(Self::HeadTail),
+            other => Err(format!("unknown truncate strategy: {other}")),
+        }
+    }
+}
+
+/// `[truncate]` の 1 行 (`パターン = 方式 [行数]`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TruncateRule {
+    pub pattern: String,
+    pub strategy: Truncate,
+    /// 省略時は `max_lines`
+    pub max_lines: Option<usize>,
 }
 
 /* ---------- Effective gather-mode config ---------- */
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ConfigParams {
     pub max_lines: usize,
     pub max_file_size: Option<u64>,
@@ -44,12 +284,75 @@ pub struct ConfigParams {
     pub skip_content_patterns: Vec<String>,
     pub include_patterns: Vec<String>,
     pub outline_patterns: Vec<String>, // ←★ new
+    pub outline_detail: OutlineDetail,
+    pub outline_layout: OutlineLayout,
+    pub outline_docs: OutlineDocs,
+    /// tiktoken 形式の語彙ファイル (未指定なら chars/4 で見積もり)
+    pub token_vocab: Option<PathBuf>,
+    /// サマリーに出す「重いファイル」の件数
+    pub token_top_n: usize,
+    /// ツリーに各ファイル・ディレクトリのトークン数を注記する
+    pub tree_tokens: bool,
+    /// 出力全体のトークン上限 (超える分は outline → tree-only に縮退)
+    pub max_tokens: Option<usize>,
+    /// 予算縮退で最後まで残すパターン (先頭ほど優先)
+    pub priority_patterns: Vec<String>,
+    /// `[languages]` の (パターン, 言語)。組み込みの対応表より優先
+    pub language_overrides: Vec<(String, String)>,
+    /// 出力を `output_part1.txt` … に分割する上限
+    pub split: Option<SplitLimit>,
+    pub split_header: SplitHeader,
+    pub output_format: OutputFormat,
+    /// 本文の各行に右寄せの行番号を付ける
+    pub line_numbers: bool,
+    /// `max_lines` を超えた本文の残し方
+    pub truncate: Truncate,
+    /// `[truncate]` のパターン別設定 (後に書いたものが優先)
+    pub truncate_rules: Vec<TruncateRule>,
     pub use_timestamp: bool,
     pub open_output: bool,
     pub use_gitignore: bool,
     pub first_run_completed: bool,
     pub max_files_per_dir: usize,
     pub max_auto_file_size: u64,
+    /// 各値の出どころ。キーは設定名、リストの要素は `exclude_patterns[0]` の形。
+    /// 記録の無いものは既定値 (CLI 指定はサブディレクトリの `.gather` より優先)
+    #[serde(skip)]
+    pub sources: BTreeMap<String, Source>,
+}
+
+/// 設定値の出どころ (`gather config` で表示)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Source {
+    Default,
+    /// `.gather` の行 (1 始まり)
+    Gather {
+        line: usize,
+    },
+    Cli,
+    Gitignore,
+}
+
+impl std::fmt::Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Source::Default => write!(f, "default"),
+            Source::Gather { line } => write!(f, ".gather line {line}"),
+            Source::Cli => write!(f, "CLI"),
+            Source::Gitignore => write!(f, ".gitignore"),
+        }
+    }
+}
+
+/// `gather config` の出力形式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConfigFormat {
+    /// `key = value  # 出どころ` の一覧 (既定)
+    #[default]
+    Text,
+    /// 値と出どころを持つ JSON
+    Json,
 }
 
 impl Default for ConfigParams {
@@ -63,12 +366,28 @@ impl Default for ConfigParams {
             skip_content_patterns: vec![],
             include_patterns: vec![],
             outline_patterns: vec![], // ←★
+            outline_detail: OutlineDetail::default(),
+            outline_layout: OutlineLayout::default(),
+            outline_docs: OutlineDocs::default(),
+            token_vocab: None,
+            token_top_n: 10,
+            tree_tokens: false,
+            max_tokens: None,
+            priority_patterns: vec![],
+            language_overrides: vec![],
+            split: None,
+            split_header: SplitHeader::default(),
+            output_format: OutputFormat::default(),
+            line_numbers: false,
+            truncate: Truncate::default(),
+            truncate_rules: vec![],
             use_timestamp: false,
             open_output: true,
             use_gitignore: false,
             first_run_completed: false,
             max_files_per_dir: 100,
             max_auto_file_size: 1_000_000,
+            sources: BTreeMap::new(),
         }
     }
 }
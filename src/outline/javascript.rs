@@ -0,0 +1,418 @@
+//! src/outline/javascript.rs
+//!
+//! JavaScript / TypeScript のアウトライン実装
+//!
+//! コメント・文字列・テンプレートリテラル・正規表現を読み飛ばしながら
+//! 括弧の深さを追跡し、**トップレベルの文** だけを見る軽量スキャナ。
+//!
+//! - `export` された function / class / interface / type / enum / 変数 / namespace
+//! - `export default …` と `export { … } from "…"` などの再エクスポート
+//! - `import … from "…"` (モジュール指定子)
+//! - export されていないトップレベル `function`
+//! - CommonJS の `module.exports = …` / `exports.foo = …`
+
+use crate::outline::provider::{OutlineProvider, Span, Symbol};
+use std::path::Path;
+
+pub struct JsOutlineProvider;
+
+/// 1 文の先頭として保持する最大文字数 (関数本体まで連結しないため)
+const MAX_STATEMENT_HEAD: usize = 2048;
+
+impl OutlineProvider for JsOutlineProvider {
+    fn supports(path: &Path) -> bool {
+        matches!(
+            path.extension().and_then(|e| e.to_str()),
+            Some("js" | "jsx" | "ts" | "tsx" | "mjs" | "cjs" | "mts" | "cts")
+        )
+    }
+
+    fn extract(_path: &Path, src: &str) -> anyhow::Result<Vec<Symbol>> {
+        Ok(top_level_statements(src)
+            .into_iter()
+            .filter_map(|(stmt, span)| {
+                let (kind, ident) = parse_statement(&stmt)?;
+                Some(Symbol {
+                    kind: kind.into(),
+                    path: ident.clone(),
+                    ident,
+                    span: Some(span),
+                    signature: None,
+                    doc: None,
+                    children: vec![],
+                })
+            })
+            .collect())
+    }
+}
+
+/* ------------------------------------------------------------------ */
+/* statement parser                                                   */
+/* ------------------------------------------------------------------ */
+
+/// 空白正規化済みのトップレベル文から (kind, ident) を取り出す
+fn parse_statement(stmt: &str) -> Option<(&'static str, String)> {
+    if let Some(rest) = stmt.strip_prefix("export ") {
+        return parse_export(rest.trim_start());
+    }
+    if let Some(rest) = stmt.strip_prefix("import") {
+        // `import(...)` (dynamic import) や `importFoo` は除外
+        if rest.starts_with([' ', '{', '*', '"', '\'']) {
+            return last_string_literal(rest).map(|m| ("import", m));
+        }
+        return None;
+    }
+    if let Some(rest) = stmt.strip_prefix("module.exports") {
+        return rest
+            .trim_start()
+            .starts_with('=')
+            .then(|| ("default", "module.exports".to_string()));
+    }
+    if let Some(rest) = stmt.strip_prefix("exports.") {
+        let name = leading_ident(rest)?;
+        return Some(("export", name.to_string()));
+    }
+    let rest = stmt.strip_prefix("declare ").unwrap_or(stmt);
+    function_name(rest).map(|n| ("function", n))
+}
+
+fn parse_export(rest: &str) -> Option<(&'static str, String)> {
+    /* --- export default ------------------------------------------- */
+    if let Some(d) = rest.strip_prefix("default ") {
+        let d = d.trim_start();
+        let name = function_name(d)
+            .or_else(|| class_name(d))
+            .or_else(|| {
+                let id = leading_ident(d)?;
+                d[id.len()..]
+                    .trim_start_matches(';')
+                    .trim()
+                    .is_empty()
+                    .then(|| id.to_string())
+            })
+            .unwrap_or_else(|| "default".to_string());
+        return Some(("default", name));
+    }
+    if let Some(d) = rest.strip_prefix('=') {
+        // TypeScript: `export = Foo;`
+        let id = leading_ident(d.trim_start()).unwrap_or("default");
+        return Some(("default", id.to_string()));
+    }
+
+    /* --- 再エクスポート -------------------------------------------- */
+    let list = rest.strip_prefix("type ").unwrap_or(rest);
+    if list.starts_with('{') || list.starts_with('*') {
+        let clause = rest.split(';').next().unwrap_or(rest).trim();
+        return Some(("export", clause.to_string()));
+    }
+
+    /* --- 宣言 ------------------------------------------------------ */
+    let decl = rest.strip_prefix("declare ").unwrap_or(rest);
+    if let Some(n) = function_name(decl) {
+        return Some(("function", n));
+    }
+    if let Some(n) = class_name(decl) {
+        return Some(("class", n));
+    }
+    let keyword = |kw: &str| {
+        decl.strip_prefix(kw)
+            .and_then(|r| r.strip_prefix(' '))
+            .and_then(leading_ident)
+            .map(str::to_string)
+    };
+    if let Some(n) = keyword("interface") {
+        return Some(("interface", n));
+    }
+    if let Some(n) = keyword("type") {
+        return Some(("type", n));
+    }
+    if let Some(n) = keyword("enum").or_else(|| keyword("const enum")) {
+        return Some(("enum", n));
+    }
+    if let Some(n) = keyword("namespace").or_else(|| keyword("module")) {
+        return Some(("namespace", n));
+    }
+    for kw in ["const", "let", "var"] {
+        if let Some(r) = decl.strip_prefix(kw).and_then(|r| r.strip_prefix(' ')) {
+            let binding = match leading_ident(r) {
+                Some(n) => n.to_string(),
+                // 分割代入: `export const { a, b } = obj`
+                None => r.split('=').next().unwrap_or(r).trim().to_string(),
+            };
+            return Some((kw, binding));
+        }
+    }
+    None
+}
+
+/// `function foo` / `async function foo` / `function* foo`
+fn function_name(s: &str) -> Option<String> {
+    let s = s.strip_prefix("async ").unwrap_or(s);
+    let rest = s.strip_prefix("function")?;
+    let rest = rest.trim_start().trim_start_matches('*').trim_start();
+    leading_ident(rest).map(str::to_string)
+}
+
+/// `class Foo` / `abstract class Foo`
+fn class_name(s: &str) -> Option<String> {
+    let s = s.strip_prefix("abstract ").unwrap_or(s);
+    let rest = s.strip_prefix("class ")?;
+    leading_ident(rest.trim_start()).map(str::to_string)
+}
+
+fn leading_ident(s: &str) -> Option<&str> {
+    let end = s
+        .char_indices()
+        .find(|&(_, c)| !(c.is_alphanumeric() || c == '_' || c == '$'))
+        .map(|(i, _)| i)
+        .unwrap_or(s.len());
+    let ident = &s[..end];
+    match ident.chars().next() {
+        Some(c) if !c.is_ascii_digit() => Some(ident),
+        _ => None,
+    }
+}
+
+/// 文中で最後に現れる '…' / "…" の中身
+fn last_string_literal(s: &str) -> Option<String> {
+    let end = s.rfind(['"', '\''])?;
+    let quote = s[end..].chars().next()?;
+    let start = s[..end].rfind(quote)?;
+    Some(s[start + 1..end].to_string())
+}
+
+/* ------------------------------------------------------------------ */
+/* lexer                                                              */
+/* ------------------------------------------------------------------ */
+
+/// ソースをトップレベル文ごとに分割し、空白を正規化して返す。
+///
+/// 行頭の括弧深さが 0 (かつコメント／テンプレート外) の行を文の開始とみなし、
+/// 後続の「深さ > 0 で始まる行」を連結する。範囲は文の最終行 (コード部分) まで。
+fn top_level_statements(src: &str) -> Vec<(String, Span)> {
+    let mut lexer = Lexer::default();
+    let mut stmts: Vec<(String, Span)> = Vec::new();
+
+    for (n, line) in src.lines().enumerate() {
+        let starts_top = lexer.depth <= 0 && !lexer.block_comment && !lexer.in_template;
+        let code = lexer.feed(line);
+        let trimmed = code.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let end_col = code.trim_end().chars().count();
+
+        if starts_top {
+            let indent = code.chars().take_while(|c| c.is_whitespace()).count();
+            let span = Span {
+                start_line: n + 1,
+                start_col: indent + 1,
+                end_line: n + 1,
+                end_col,
+            };
+            stmts.push((trimmed.to_string(), span));
+        } else if let Some((last, span)) = stmts.last_mut() {
+            span.end_line = n + 1;
+            span.end_col = end_col;
+            if last.len() < MAX_STATEMENT_HEAD {
+                last.push(' ');
+                last.push_str(trimmed);
+            }
+        }
+    }
+
+    stmts
+        .into_iter()
+        .map(|(s, span)| (s.split_whitespace().collect::<Vec<_>>().join(" "), span))
+        .collect()
+}
+
+#[derive(Default)]
+struct Lexer {
+    depth: i32,
+    block_comment: bool,
+    in_template: bool,
+    /// `${` を開いた時点の depth
+    template_exprs: Vec<i32>,
+}
+
+impl Lexer {
+    /// 1 行を処理し、コメントを除いたコード部分を返す
+    fn feed(&mut self, line: &str) -> String {
+        let chars: Vec<char> = line.chars().collect();
+        let mut out = String::with_capacity(line.len());
+        let mut i = 0;
+
+        while i < chars.len() {
+            let c = chars[i];
+            let next = chars.get(i + 1).copied();
+
+            if self.block_comment {
+                if c == '*' && next == Some('/') {
+                    self.block_comment = false;
+                    i += 1;
+                }
+                i += 1;
+                continue;
+            }
+
+            if self.in_template {
+                out.push(c);
+                match (c, next) {
+                    ('\\', Some(n)) => {
+                        out.push(n);
+                        i += 1;
+                    }
+                    ('`', _) => self.in_template = false,
+                    ('$', Some('{')) => {
+                        out.push('{');
+                        self.template_exprs.push(self.depth);
+                        self.depth += 1;
+                        self.in_template = false;
+                        i += 1;
+                    }
+                    _ => {}
+                }
+                i += 1;
+                continue;
+            }
+
+            match (c, next) {
+                ('/', Some('/')) => break,
+                ('/', Some('*')) => {
+                    self.block_comment = true;
+                    i += 2;
+                    continue;
+                }
+                ('/', _) if regex_allowed(&out) => {
+                    // 正規表現リテラルを読み飛ばす
+                    out.push(c);
+                    i += 1;
+                    let mut in_class = false;
+                    while i < chars.len() {
+                        let r = chars[i];
+                        out.push(r);
+                        match r {
+                            '\\' => {
+                                if let Some(&n) = chars.get(i + 1) {
+                                    out.push(n);
+                                    i += 1;
+                                }
+                            }
+                            '[' => in_class = true,
+                            ']' => in_class = false,
+                            '/' if !in_class => break,
+                            _ => {}
+                        }
+                        i += 1;
+                    }
+                }
+                ('"' | '\'', _) => {
+                    // 文字列は行末で必ず終わる (JSX テキスト中の ' 対策にもなる)
+                    out.push(c);
+                    i += 1;
+                    while i < chars.len() {
+                        let s = chars[i];
+                        out.push(s);
+                        if s == '\\' {
+                            if let Some(&n) = chars.get(i + 1) {
+                                out.push(n);
+                                i += 1;
+                            }
+                        } else if s == c {
+                            break;
+                        }
+                        i += 1;
+                    }
+                }
+                ('`', _) => {
+                    out.push(c);
+                    self.in_template = true;
+                }
+                ('{' | '(' | '[', _) => {
+                    out.push(c);
+                    self.depth += 1;
+                }
+                ('}', _) => {
+                    out.push(c);
+                    self.depth -= 1;
+                    if self.template_exprs.last() == Some(&self.depth) {
+                        self.template_exprs.pop();
+                        self.in_template = true;
+                    }
+                }
+                (')' | ']', _) => {
+                    out.push(c);
+                    self.depth -= 1;
+                }
+                _ => out.push(c),
+            }
+            i += 1;
+        }
+        out
+    }
+}
+
+/// 直前のトークンから `/` が正規表現の開始になり得るかを推定する
+fn regex_allowed(before: &str) -> bool {
+    let t = before.trim_end();
+    match t.chars().last() {
+        None => true,
+        Some(c) if "(,=:[!&|?{};+-*%<>~^".contains(c) => true,
+        Some(_) => t.ends_with("return") || t.ends_with("typeof"),
+    }
+}
+
+/* ------------------------------------------------------------------ */
+/* tests                                                              */
+/* ------------------------------------------------------------------ */
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collect_exports_imports_and_top_level_functions() {
+        let src = r#"
+import React from "react";
+import {
+  useState,
+  useEffect,
+} from 'react';
+
+/* export function commented() {} */
+export interface Props { name: string }
+export type Id = string | number;
+export const enum Color { Red }
+export async function load(url: string): Promise<void> {
+  const re = /[{}]/g;
+  const s = `${url} }`;
+  function nested() {}
+}
+export default class App {}
+export { helper as h } from "./util";
+export const API_URL = "https://example.com/{x}";
+
+function local() {}
+const notExported = () => {};
+"#;
+        let syms = JsOutlineProvider::extract(Path::new("app.tsx"), src).unwrap();
+        let pairs: Vec<_> = syms.iter().map(|s| (&s.kind[..], &s.ident[..])).collect();
+        assert_eq!(
+            pairs,
+            vec![
+                ("import", "react"),
+                ("import", "react"),
+                ("interface", "Props"),
+                ("type", "Id"),
+                ("enum", "Color"),
+                ("function", "load"),
+                ("default", "App"),
+                ("export", "{ helper as h } from \"./util\""),
+                ("const", "API_URL"),
+                ("function", "local"),
+            ]
+        );
+        let load = syms.iter().find(|s| s.ident == "load").unwrap();
+        assert_eq!(load.span.unwrap().lines_label(), "L12-L16");
+    }
+}
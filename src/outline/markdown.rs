@@ -0,0 +1,212 @@
+//! src/outline/markdown.rs
+//!
+//! Markdown の見出しアウトライン (ATX / Setext)
+//!
+//! 見出し 1 つにつき `Symbol { kind: "h<level>", ident: <見出しテキスト> }` を返す。
+//! 下位レベルの見出しは上位見出しの子になり、`path` は `Title > Install` の形。
+//! フェンスドコードブロック内と先頭の YAML front matter は無視する。
+
+use crate::outline::provider::{nest, OutlineProvider, Span, Symbol};
+use std::path::Path;
+
+pub struct MarkdownOutlineProvider;
+
+impl OutlineProvider for MarkdownOutlineProvider {
+    fn supports(path: &Path) -> bool {
+        matches!(
+            path.extension().and_then(|e| e.to_str()),
+            Some("md" | "markdown" | "mdx")
+        )
+    }
+
+    fn extract(_path: &Path, src: &str) -> anyhow::Result<Vec<Symbol>> {
+        let all: Vec<&str> = src.lines().collect();
+        // (level, 見出しテキスト, 開始行 index)
+        let mut headings: Vec<(usize, &str, usize)> = Vec::new();
+        let mut lines = all.iter().copied().enumerate().peekable();
+
+        /* --- YAML front matter -------------------------------------- */
+        if lines.peek().map(|(_, l)| l.trim_end()) == Some("---") {
+            lines.next();
+            for (_, l) in lines.by_ref() {
+                if matches!(l.trim_end(), "---" | "...") {
+                    break;
+                }
+            }
+        }
+
+        let mut fence: Option<(char, usize)> = None;
+        // 直前の段落行 (Setext 見出しの候補)
+        let mut paragraph: Option<(usize, &str)> = None;
+
+        for (idx, line) in lines {
+            /* --- フェンス ------------------------------------------ */
+            if let Some((ch, len)) = fence {
+                if closes_fence(line, ch, len) {
+                    fence = None;
+                }
+                continue;
+            }
+            if let Some(open) = opens_fence(line) {
+                fence = Some(open);
+                paragraph = None;
+                continue;
+            }
+
+            /* --- ATX ---------------------------------------------- */
+            if let Some((level, text)) = atx_heading(line) {
+                headings.push((level, text, idx));
+                paragraph = None;
+                continue;
+            }
+
+            /* --- Setext ------------------------------------------- */
+            if let (Some((start, text)), Some(level)) = (paragraph, setext_level(line)) {
+                headings.push((level, text.trim(), start));
+                paragraph = None;
+                continue;
+            }
+
+            paragraph = if line.trim().is_empty() || line.starts_with("    ") {
+                None
+            } else {
+                Some((idx, line))
+            };
+        }
+
+        /* --- 親: 直前にある、より浅いレベルの見出し ----------------- */
+        let parents: Vec<Option<usize>> = (0..headings.len())
+            .map(|i| (0..i).rev().find(|&j| headings[j].0 < headings[i].0))
+            .collect();
+
+        /* --- 範囲: 次の同レベル以上の見出しの手前まで (末尾の空行は除く) --- */
+        let mut symbols: Vec<Symbol> = Vec::with_capacity(headings.len());
+        for (i, &(level, text, start)) in headings.iter().enumerate() {
+            let next = headings[i + 1..]
+                .iter()
+                .find(|(l, _, _)| *l <= level)
+                .map_or(all.len(), |&(_, _, s)| s);
+            let mut end = next.saturating_sub(1).max(start);
+            while end > start && all[end].trim().is_empty() {
+                end -= 1;
+            }
+            let path = match parents[i] {
+                Some(p) => format!("{} > {text}", symbols[p].path),
+                None => text.to_string(),
+            };
+            symbols.push(Symbol {
+                kind: format!("h{level}"),
+                ident: text.to_string(),
+                path,
+                span: Some(Span {
+                    start_line: start + 1,
+                    start_col: 1,
+                    end_line: end + 1,
+                    end_col: all[end].chars().count(),
+                }),
+                signature: None,
+                doc: None,
+                children: vec![],
+            });
+        }
+        Ok(nest(symbols, &parents))
+    }
+}
+
+/* ------------------------------------------------------------------ */
+
+/// 先頭 3 スペースまでのインデントを外す
+fn strip_indent(line: &str) -> Option<&str> {
+    let body = line.trim_start_matches(' ');
+    (line.len() - body.len() <= 3).then_some(body)
+}
+
+fn opens_fence(line: &str) -> Option<(char, usize)> {
+    let body = strip_indent(line)?;
+    let ch = body.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let len = body.chars().take_while(|c| *c == ch).count();
+    // バッククォートのフェンスは info string に ` を含められない
+    (len >= 3 && !(ch == '`' && body[len..].contains('`'))).then_some((ch, len))
+}
+
+fn closes_fence(line: &str, ch: char, len: usize) -> bool {
+    strip_indent(line).is_some_and(|body| {
+        let run = body.chars().take_while(|c| *c == ch).count();
+        run >= len && body[run * ch.len_utf8()..].trim().is_empty()
+    })
+}
+
+fn atx_heading(line: &str) -> Option<(usize, &str)> {
+    let body = strip_indent(line)?;
+    let level = body.chars().take_while(|c| *c == '#').count();
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+    let rest = &body[level..];
+    if !(rest.is_empty() || rest.starts_with([' ', '\t'])) {
+        return None;
+    }
+    // 閉じ `#` 列 (直前が空白のときのみ) を除去
+    let rest = rest.trim();
+    let trimmed = rest.trim_end_matches('#');
+    let text = if trimmed.is_empty() || trimmed.ends_with([' ', '\t']) {
+        trimmed.trim_end()
+    } else {
+        rest
+    };
+    Some((level, text))
+}
+
+fn setext_level(line: &str) -> Option<usize> {
+    let body = strip_indent(line)?.trim_end();
+    match body.chars().next()? {
+        '=' if body.chars().all(|c| c == '=') => Some(1),
+        '-' if body.chars().all(|c| c == '-') => Some(2),
+        _ => None,
+    }
+}
+
+/* ------------------------------------------------------------------ */
+/* tests                                                              */
+/* ------------------------------------------------------------------ */
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collect_atx_and_setext_headings_outside_fences() {
+        let src = r#"---
+title: front matter
+---
+Title
+=====
+
+## Install ##
+
+```bash
+# not a heading
+```
+
+Usage
+-----
+
+### C# notes
+#hashtag
+"#;
+        let syms = MarkdownOutlineProvider::extract(Path::new("README.md"), src).unwrap();
+        assert_eq!(syms.len(), 1, "everything nests under the h1");
+        let syms = Symbol::flatten(syms);
+        let pairs: Vec<_> = syms.iter().map(|s| (&s.kind[..], &s.path[..])).collect();
+        assert_eq!(
+            pairs,
+            vec![
+                ("h1", "Title"),
+                ("h2", "Title > Install"),
+                ("h2", "Title > Usage"),
+                ("h3", "Title > Usage > C# notes"),
+            ]
+        );
+        let ranges: Vec<_> = syms.iter().map(|s| s.span.unwrap().lines_label()).collect();
+        assert_eq!(ranges, vec!["L4-L17", "L7-L11", "L13-L17", "L16-L17"]);
+    }
+}
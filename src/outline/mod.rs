@@ -1,37 +1,92 @@
+mod javascript;
+mod markdown;
 mod provider;
+mod python;
 pub mod registry;
 mod rust; // ← pub にした
 
-use crate::model::OutlineFormat;
-use provider::Symbol;
+use crate::gitignore::GitIgnore;
+use crate::model::{ConfigParams, OutlineDetail, OutlineDocs, OutlineFormat, OutlineLayout};
+use crate::scanner::scope::Scopes;
+use crate::scanner::sort::compare_dir_entry;
+use crate::scanner::walker::collect_entries;
+pub(crate) use provider::Symbol;
 use registry::providers; // 共有プロバイダ
 use serde_json::json;
 use std::fs;
 use std::io::Write;
 use std::path::Path;
-use walkdir::WalkDir;
 
-/* ----------- 以下は元のまま ----------- */
+/// 出力時の見せ方 (outline モード / `[outline]` セクション共通)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RenderOptions {
+    pub detail: OutlineDetail,
+    pub layout: OutlineLayout,
+    pub docs: OutlineDocs,
+}
+
+impl From<&ConfigParams> for RenderOptions {
+    fn from(cfg: &ConfigParams) -> Self {
+        Self {
+            detail: cfg.outline_detail,
+            layout: cfg.outline_layout,
+            docs: cfg.outline_docs,
+        }
+    }
+}
+
+impl RenderOptions {
+    /// レイアウト・詳細度に合わせてシンボル木を整える
+    pub(crate) fn shape(&self, symbols: Vec<Symbol>) -> Vec<Symbol> {
+        let mut symbols = match self.layout {
+            OutlineLayout::Tree => symbols,
+            OutlineLayout::Flat => Symbol::flatten(symbols),
+        };
+        trim_symbols(&mut symbols, self);
+        symbols
+    }
+}
+
+/// outline モード本体
+///
+/// gather モードと同じく `[exclude]` / `.gitignore` / `[include]` を反映し、
+/// ナチュラルソート順に対応ファイルのアウトラインを書き出す。
+/// 出力ファイル自身は (除外設定が無くても) 対象にしない。
+pub fn run(
+    dir: &Path,
+    output: &Path,
+    fmt: OutlineFormat,
+    cfg: &ConfigParams,
+) -> anyhow::Result<()> {
+    let render = RenderOptions::from(cfg);
+    let scopes = Scopes::new(dir, cfg);
+    let gitignore = cfg.use_gitignore.then(|| GitIgnore::new(dir));
+
+    let mut entries = collect_entries(dir, &scopes, gitignore.as_ref(), true);
+    entries.sort_by(|a, b| compare_dir_entry(a, b, dir));
 
-pub fn run(dir: &Path, output: &Path, fmt: OutlineFormat) -> anyhow::Result<()> {
     let mut out = fs::File::create(output)?;
+    let output = output.canonicalize()?;
 
-    for entry in WalkDir::new(dir)
-        .into_iter()
-        .filter_map(|e| e.ok())
-        .filter(|e| e.file_type().is_file())
-    {
+    for entry in &entries {
         let path = entry.path();
-        let src = fs::read_to_string(path).unwrap_or_default();
+        let rel = path.strip_prefix(dir).unwrap_or(path);
+        if !scopes.is_included(rel) {
+            continue;
+        }
+        if path.canonicalize().is_ok_and(|p| p == output) {
+            continue;
+        }
 
         if let Some(p) = providers().iter().find(|p| p.supports_dyn(path)) {
+            let src = fs::read_to_string(path).unwrap_or_default();
             let symbols = p.extract_dyn(path, &src)?;
             if symbols.is_empty() {
                 continue;
             }
             match fmt {
-                OutlineFormat::Md => write_md(&mut out, path, symbols)?,
-                OutlineFormat::Json => write_json(&mut out, path, symbols)?,
+                OutlineFormat::Md => write_md(&mut out, rel, symbols, render)?,
+                OutlineFormat::Json => write_json(&mut out, rel, symbols, render)?,
             }
         }
     }
@@ -40,19 +95,109 @@ pub fn run(dir: &Path, output: &Path, fmt: OutlineFormat) -> anyhow::Result<()>
 
 /* ---------------- writers ------------------------------------------ */
 
-fn write_md(out: &mut fs::File, path: &Path, symbols: Vec<Symbol>) -> std::io::Result<()> {
-    writeln!(out, "### {}", path.display())?;
+fn write_md(
+    out: &mut fs::File,
+    path: &Path,
+    symbols: Vec<Symbol>,
+    render: RenderOptions,
+) -> std::io::Result<()> {
+    let file = path.display().to_string();
+    writeln!(out, "### {file}")?;
+    write_md_symbols(out, &file, symbols, render)?;
+    writeln!(out)?;
+    Ok(())
+}
+
+/// シンボル一覧を Markdown の箇条書きで書き出す (scanner の `[outline]` からも使用)
+///
+/// 位置情報があれば `- **fn** gather (src/lib.rs:L10-L42)` の形で付与する。
+/// doc コメントがあれば 2 スペース字下げでその下に (`OutlineDocs` に従い要約 / 全文)、
+/// `OutlineDetail::Signatures` ではシグネチャを 6 スペース字下げで続けて出力する。
+/// 木レイアウトでは子シンボルを 2 スペースずつ字下げし、
+/// 平坦レイアウトでは修飾名 (`foo::bar`) で 1 段に並べる。
+pub(crate) fn write_md_symbols<W: Write>(
+    out: &mut W,
+    file: &str,
+    symbols: Vec<Symbol>,
+    render: RenderOptions,
+) -> std::io::Result<()> {
+    let symbols = render.shape(symbols);
+    let flat = render.layout == OutlineLayout::Flat;
+    write_md_level(out, file, &symbols, flat, 0)
+}
+
+fn write_md_level<W: Write>(
+    out: &mut W,
+    file: &str,
+    symbols: &[Symbol],
+    flat: bool,
+    depth: usize,
+) -> std::io::Result<()> {
+    let indent = "  ".repeat(depth);
     for s in symbols {
-        writeln!(out, "- **{}** {}", s.kind, s.ident)?;
+        let name = if flat { &s.path } else { &s.ident };
+        match &s.span {
+            Some(span) => writeln!(
+                out,
+                "{indent}- **{}** {} ({}:{})",
+                s.kind,
+                name,
+                file,
+                span.lines_label()
+            )?,
+            None => writeln!(out, "{indent}- **{}** {}", s.kind, name)?,
+        }
+        if let Some(doc) = &s.doc {
+            for line in doc.lines() {
+                if line.is_empty() {
+                    writeln!(out)?;
+                } else {
+                    writeln!(out, "{indent}  {line}")?;
+                }
+            }
+        }
+        if let Some(sig) = &s.signature {
+            for line in sig.lines() {
+                writeln!(out, "{indent}      {line}")?;
+            }
+        }
+        write_md_level(out, file, &s.children, flat, depth + 1)?;
     }
-    writeln!(out)?;
     Ok(())
 }
 
-fn write_json(out: &mut fs::File, path: &Path, symbols: Vec<Symbol>) -> std::io::Result<()> {
+fn write_json(
+    out: &mut fs::File,
+    path: &Path,
+    symbols: Vec<Symbol>,
+    render: RenderOptions,
+) -> std::io::Result<()> {
     let v = json!({
         "file": path.to_string_lossy(),
-        "symbols": symbols.iter().map(|s| json!({"kind": s.kind, "ident": s.ident})).collect::<Vec<_>>()
+        "symbols": render.shape(symbols),
     });
     writeln!(out, "{}", v)
 }
+
+/// 詳細度に応じてシグネチャを外し、doc コメントを要約 / 除去する
+fn trim_symbols(symbols: &mut [Symbol], render: &RenderOptions) {
+    for s in symbols {
+        if render.detail == OutlineDetail::Names {
+            s.signature = None;
+        }
+        s.doc = match render.docs {
+            OutlineDocs::Full => s.doc.take(),
+            OutlineDocs::Summary => s.doc.as_deref().map(first_paragraph),
+            OutlineDocs::None => None,
+        };
+        trim_symbols(&mut s.children, render);
+    }
+}
+
+/// 最初の空行までを 1 段落として返す
+fn first_paragraph(doc: &str) -> String {
+    doc.lines()
+        .take_while(|l| !l.trim().is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -2,13 +2,79 @@
 //!
 //! Outline 用共通インターフェース。
 
+use serde::Serialize;
 use std::path::Path;
 
+/// ソース上の範囲 (行・列とも 1 始まり、end は最終行／最終列を含む)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Span {
+    pub start_line: usize,
+    pub start_col: usize,
+    pub end_line: usize,
+    pub end_col: usize,
+}
+
+impl Span {
+    /// `L10-L42` / 1 行なら `L10`
+    pub fn lines_label(&self) -> String {
+        if self.start_line == self.end_line {
+            format!("L{}", self.start_line)
+        } else {
+            format!("L{}-L{}", self.start_line, self.end_line)
+        }
+    }
+}
+
 /// 抽出されたシンボル 1 件
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Symbol {
     pub kind: String,
     pub ident: String,
+    /// 親をたどった修飾名 (`foo::bar` / `Class.method` など。区切りは言語依存)
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub span: Option<Span>,
+    /// 本体を省いたシグネチャ (対応プロバイダのみ)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    /// doc コメント本文 (`///` / `#[doc = ".."]`、対応プロバイダのみ)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doc: Option<String>,
+    /// 子シンボル (モジュールの中身、impl ブロックとそのメソッドなど)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<Symbol>,
+}
+
+impl Symbol {
+    /// 木を行きがけ順に平坦化する (子は親の直後に並ぶ)
+    pub fn flatten(symbols: Vec<Symbol>) -> Vec<Symbol> {
+        let mut flat = Vec::new();
+        for mut s in symbols {
+            let children = std::mem::take(&mut s.children);
+            flat.push(s);
+            flat.extend(Symbol::flatten(children));
+        }
+        flat
+    }
+}
+
+/// 親 index 付きの平坦なリストから木を組み立てる (`parents[i] < i` が前提)
+pub fn nest(symbols: Vec<Symbol>, parents: &[Option<usize>]) -> Vec<Symbol> {
+    let mut slots: Vec<Option<Symbol>> = symbols.into_iter().map(Some).collect();
+    let mut roots = Vec::new();
+    for i in (0..slots.len()).rev() {
+        let sym = slots[i].take().expect("each slot is taken once");
+        match parents[i] {
+            Some(p) => slots[p]
+                .as_mut()
+                .expect("parent precedes child")
+                .children
+                .insert(0, sym),
+            None => roots.push(sym),
+        }
+    }
+    roots.reverse();
+    roots
 }
 
 /// 言語ごとのアウトライン抽出器トレイト
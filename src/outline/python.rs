@@ -0,0 +1,426 @@
+//! src/outline/python.rs
+//!
+//! Python のアウトライン実装 (行ベースの軽量パーサ)
+//!
+//! - トップレベルの `class` / `def` / `async def`
+//! - トップレベル class 直下のメソッド (class の子。`path` は `Class.method`)
+//! - モジュールレベル定数 (`UPPER_CASE = ...`)
+//!
+//! Python には `pub` が無いため、`_private` な名前も含めてすべて列挙する。
+//! `if TYPE_CHECKING:` や `try:` など複合文の中身は条件付きの定義なので対象外。
+
+use crate::outline::provider::{nest, OutlineProvider, Span, Symbol};
+use std::path::Path;
+
+pub struct PythonOutlineProvider;
+
+impl OutlineProvider for PythonOutlineProvider {
+    fn supports(path: &Path) -> bool {
+        matches!(path.extension(), Some(ext) if ext == "py" || ext == "pyi")
+    }
+
+    fn extract(_path: &Path, src: &str) -> anyhow::Result<Vec<Symbol>> {
+        let mut symbols = Vec::new();
+        // symbols[i] の親 (メソッド → クラス)
+        let mut parents: Vec<Option<usize>> = Vec::new();
+        let mut lexer = LineLexer::default();
+        // 現在開いているブロック (class / def / 複合文)
+        let mut blocks: Vec<Block> = Vec::new();
+        // 終端行が未確定の定数 (複数行にまたがる代入)
+        let mut open_const: Option<usize> = None;
+        // 直近の中身のある行 (行番号, 行末の列)
+        let mut last = (0, 0);
+
+        for (n, line) in src.lines().enumerate() {
+            let lineno = n + 1;
+            let logical_start = lexer.at_statement_start();
+            lexer.feed(line);
+
+            let body = line.trim_start();
+            if body.is_empty() || (logical_start && body.starts_with('#')) {
+                continue;
+            }
+            // 複数行シグネチャや docstring の続きは範囲の延長だけ
+            if logical_start {
+                let indent = line.len() - body.len();
+                close_span(&mut symbols, open_const.take(), last);
+                while matches!(blocks.last(), Some(b) if b.indent >= indent) {
+                    let b = blocks.pop().expect("checked above");
+                    close_span(&mut symbols, b.symbol, last);
+                }
+
+                let start = Span {
+                    start_line: lineno,
+                    start_col: indent + 1,
+                    end_line: lineno,
+                    end_col: line.chars().count(),
+                };
+
+                if let Some((kind, name)) = parse_def(body) {
+                    // (親クラスの index, 親クラス名)
+                    let owner = match blocks.as_slice() {
+                        [] => Some(None),
+                        [b] if b.kind == Some(BlockKind::Class) => {
+                            b.symbol.map(|idx| Some((idx, b.name.clone())))
+                        }
+                        _ => None, // 関数内の関数・複合文の中身などは対象外
+                    };
+                    let mut symbol = None;
+                    if let Some(owner) = owner {
+                        let (kind, path, parent) = match (owner, kind) {
+                            (Some((idx, cls)), BlockKind::Def { .. }) => {
+                                ("method", format!("{cls}.{name}"), Some(idx))
+                            }
+                            (Some((idx, cls)), BlockKind::Class) => {
+                                ("class", format!("{cls}.{name}"), Some(idx))
+                            }
+                            (None, k) => (k.label(), name.clone(), None),
+                        };
+                        symbol = Some(symbols.len());
+                        parents.push(parent);
+                        symbols.push(Symbol {
+                            kind: kind.into(),
+                            ident: name.clone(),
+                            path,
+                            span: Some(start),
+                            signature: None,
+                            doc: None,
+                            children: vec![],
+                        });
+                    }
+                    blocks.push(Block {
+                        indent,
+                        kind: Some(kind),
+                        name,
+                        symbol,
+                    });
+                } else if is_compound(body) {
+                    blocks.push(Block {
+                        indent,
+                        kind: None,
+                        name: String::new(),
+                        symbol: None,
+                    });
+                } else if blocks.is_empty() {
+                    if let Some(name) = parse_constant(body) {
+                        open_const = Some(symbols.len());
+                        parents.push(None);
+                        symbols.push(Symbol {
+                            kind: "const".into(),
+                            path: name.clone(),
+                            ident: name,
+                            span: Some(start),
+                            signature: None,
+                            doc: None,
+                            children: vec![],
+                        });
+                    }
+                }
+            }
+            last = (lineno, line.chars().count());
+        }
+
+        close_span(&mut symbols, open_const, last);
+        for b in blocks.into_iter().rev() {
+            close_span(&mut symbols, b.symbol, last);
+        }
+        Ok(nest(symbols, &parents))
+    }
+}
+
+/// 開いているブロック 1 段
+struct Block {
+    indent: usize,
+    /// `None` は `if` / `for` / `try` / `with` などの複合文 (中身は対象外)
+    kind: Option<BlockKind>,
+    name: String,
+    /// 対応するシンボル (出力対象外なら None)
+    symbol: Option<usize>,
+}
+
+/// シンボルの終端を確定させる
+fn close_span(symbols: &mut [Symbol], idx: Option<usize>, (line, col): (usize, usize)) {
+    if let Some(span) = idx.and_then(|i| symbols[i].span.as_mut()) {
+        if line >= span.start_line {
+            span.end_line = line;
+            span.end_col = col;
+        }
+    }
+}
+
+/* ------------------------------------------------------------------ */
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BlockKind {
+    Class,
+    Def { is_async: bool },
+}
+
+impl BlockKind {
+    fn label(self) -> &'static str {
+        match self {
+            BlockKind::Class => "class",
+            BlockKind::Def { is_async: false } => "def",
+            BlockKind::Def { is_async: true } => "async def",
+        }
+    }
+}
+
+/// `class Foo(...):` / `def foo(` / `async def foo(` を解釈
+fn parse_def(body: &str) -> Option<(BlockKind, String)> {
+    let (kind, rest) = if let Some(r) = body.strip_prefix("class ") {
+        (BlockKind::Class, r)
+    } else if let Some(r) = body.strip_prefix("def ") {
+        (BlockKind::Def { is_async: false }, r)
+    } else if let Some(r) = body
+        .strip_prefix("async ")
+        .map(str::trim_start)
+        .and_then(|r| r.strip_prefix("def "))
+    {
+        (BlockKind::Def { is_async: true }, r)
+    } else {
+        return None;
+    };
+    let name = leading_ident(rest.trim_start())?;
+    Some((kind, name.to_string()))
+}
+
+/// `if ...:` / `async with ...:` など複合文の見出しか
+fn is_compound(body: &str) -> bool {
+    let body = body
+        .strip_prefix("async ")
+        .map(str::trim_start)
+        .unwrap_or(body);
+    let word = leading_ident(body).unwrap_or("");
+    let keyword = matches!(
+        word,
+        "if" | "elif"
+            | "else"
+            | "try"
+            | "except"
+            | "finally"
+            | "with"
+            | "for"
+            | "while"
+            | "match"
+            | "case"
+    );
+    // `match = 1` のようなソフトキーワードの代入は除く
+    keyword && !body[word.len()..].trim_start().starts_with('=')
+}
+
+/// `NAME = ...` / `NAME: int = ...` (NAME は UPPER_SNAKE_CASE)
+fn parse_constant(body: &str) -> Option<String> {
+    let name = leading_ident(body)?;
+    let is_upper = name.chars().any(|c| c.is_ascii_uppercase())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_');
+    if !is_upper {
+        return None;
+    }
+    let rest = body[name.len()..].trim_start();
+    let assigned = if let Some(ann) = rest.strip_prefix(':') {
+        ann.contains('=')
+    } else {
+        rest.starts_with('=') && !rest.starts_with("==")
+    };
+    assigned.then(|| name.to_string())
+}
+
+fn leading_ident(s: &str) -> Option<&str> {
+    let end = s
+        .char_indices()
+        .find(|&(_, c)| !(c.is_alphanumeric() || c == '_'))
+        .map(|(i, _)| i)
+        .unwrap_or(s.len());
+    let ident = &s[..end];
+    match ident.chars().next() {
+        Some(c) if !c.is_ascii_digit() => Some(ident),
+        _ => None,
+    }
+}
+
+/* ------------------------------------------------------------------ */
+
+/// 括弧の深さと三重クォート文字列を行をまたいで追跡する
+#[derive(Default)]
+struct LineLexer {
+    depth: i32,
+    triple: Option<&'static str>,
+    continued: bool,
+}
+
+impl LineLexer {
+    /// 次の行が新しい文の先頭か
+    fn at_statement_start(&self) -> bool {
+        self.depth <= 0 && self.triple.is_none() && !self.continued
+    }
+
+    fn feed(&mut self, line: &str) {
+        let bytes = line.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() {
+            if let Some(q) = self.triple {
+                match line[i..].find(q) {
+                    Some(off) => {
+                        i += off + 3;
+                        self.triple = None;
+                    }
+                    None => i = bytes.len(),
+                }
+                continue;
+            }
+            match bytes[i] {
+                b'#' => break,
+                b'(' | b'[' | b'{' => self.depth += 1,
+                b')' | b']' | b'}' => self.depth -= 1,
+                q @ (b'"' | b'\'') => {
+                    let triple = if q == b'"' { "\"\"\"" } else { "'''" };
+                    if line[i..].starts_with(triple) {
+                        self.triple = Some(triple);
+                        i += 3;
+                        continue;
+                    }
+                    // 単一行文字列を読み飛ばす
+                    i += 1;
+                    while i < bytes.len() && bytes[i] != q {
+                        if bytes[i] == b'\\' {
+                            i += 1;
+                        }
+                        i += 1;
+                    }
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+        self.continued = self.triple.is_none() && line.trim_end().ends_with('\\');
+    }
+}
+
+/* ------------------------------------------------------------------ */
+/* tests                                                              */
+/* ------------------------------------------------------------------ */
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 平坦化した (kind, path)
+    fn extract(src: &str) -> Vec<(String, String)> {
+        let syms = PythonOutlineProvider::extract(Path::new("dummy.py"), src).unwrap();
+        Symbol::flatten(syms)
+            .into_iter()
+            .map(|s| (s.kind, s.path))
+            .collect()
+    }
+
+    #[test]
+    fn collect_classes_functions_and_constants() {
+        let src = r#"
+MAX_SIZE = 10
+_CACHE = {}
+TIMEOUT: float = 1.5
+lower = 1
+
+@decorator
+class Foo(Base):
+    """class Fake:
+    def not_a_method(self): ...
+    """
+    LIMIT = 3
+
+    def __init__(self, x):
+        def inner():
+            pass
+
+    async def fetch(
+        self,
+        url,
+    ):
+        pass
+
+    def _hidden(self):
+        pass
+
+def bar(a, b=(1, 2)):
+    pass
+
+async def baz():
+    pass
+
+def _private():
+    pass
+"#;
+        let pairs: Vec<_> = extract(src);
+        let pairs: Vec<_> = pairs.iter().map(|(k, i)| (&k[..], &i[..])).collect();
+        assert_eq!(
+            pairs,
+            vec![
+                ("const", "MAX_SIZE"),
+                ("const", "_CACHE"),
+                ("const", "TIMEOUT"),
+                ("class", "Foo"),
+                ("method", "Foo.__init__"),
+                ("method", "Foo.fetch"),
+                ("method", "Foo._hidden"),
+                ("def", "bar"),
+                ("async def", "baz"),
+                ("def", "_private"),
+            ]
+        );
+    }
+
+    #[test]
+    fn definitions_inside_compound_statements_are_skipped() {
+        let src = r#"
+if TYPE_CHECKING:
+    from typing import Any
+    def typed() -> Any: ...
+
+try:
+    import ujson as json
+except ImportError:
+    def loads(s): ...
+
+class Foo:
+    if sys.version_info >= (3, 11):
+        def new_api(self): ...
+    else:
+        def old_api(self): ...
+
+    def real(self):
+        if True:
+            def helper(): ...
+
+def after():
+    pass
+"#;
+        let pairs: Vec<_> = extract(src);
+        let pairs: Vec<_> = pairs.iter().map(|(k, i)| (&k[..], &i[..])).collect();
+        assert_eq!(
+            pairs,
+            vec![("class", "Foo"), ("method", "Foo.real"), ("def", "after")]
+        );
+    }
+
+    #[test]
+    fn spans_cover_whole_block() {
+        let src = "class A:\n    def f(self):\n        pass\n\n    def g(\n        self,\n    ):\n        return 1\n\nX = 1\n";
+        let syms = PythonOutlineProvider::extract(Path::new("dummy.py"), src).unwrap();
+        assert_eq!(syms.len(), 2, "methods are nested under the class");
+        assert_eq!(syms[0].children.len(), 2);
+        let lines: Vec<_> = Symbol::flatten(syms)
+            .into_iter()
+            .map(|s| {
+                let sp = s.span.unwrap();
+                (s.path, sp.start_line, sp.end_line)
+            })
+            .collect();
+        let lines: Vec<_> = lines.iter().map(|(p, s, e)| (&p[..], *s, *e)).collect();
+        assert_eq!(
+            lines,
+            vec![("A", 1, 8), ("A.f", 2, 3), ("A.g", 5, 8), ("X", 10, 10)]
+        );
+    }
+}
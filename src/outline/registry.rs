@@ -4,7 +4,10 @@ use std::path::Path;
 
 use once_cell::sync::Lazy;
 
+use super::javascript::JsOutlineProvider;
+use super::markdown::MarkdownOutlineProvider;
 use super::provider::{OutlineProvider, Symbol};
+use super::python::PythonOutlineProvider;
 use super::rust::RustOutlineProvider;
 
 /* ---------- トレイト ---------- */
@@ -34,6 +37,9 @@ type DynProviderBox = Box<dyn DynProvider>;
 static PROVS: Lazy<Vec<DynProviderBox>> = Lazy::new(|| {
     vec![
         Box::new(RustOutlineProvider) as DynProviderBox,
+        Box::new(PythonOutlineProvider),
+        Box::new(MarkdownOutlineProvider),
+        Box::new(JsOutlineProvider),
         // 今後言語を追加するときはここに push!
     ]
 });
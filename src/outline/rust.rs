@@ -2,10 +2,16 @@
 //!
 //! Rust のアウトライン実装 (syn ベース)
 
-use crate::outline::provider::{OutlineProvider, Symbol};
+use crate::outline::provider::{OutlineProvider, Span, Symbol};
 use anyhow::Context;
+use proc_macro2::{Delimiter, TokenTree};
+use quote::ToTokens;
+use std::collections::HashSet;
 use std::path::Path;
-use syn::{visit::Visit, File, Item, Visibility};
+use syn::{
+    Attribute, Expr, ExprLit, File, ImplItem, Item, ItemConst, ItemFn, ItemImpl, ItemType, Lit,
+    Meta, TraitItem, Type, Visibility,
+};
 
 pub struct RustOutlineProvider;
 
@@ -17,50 +23,416 @@ impl OutlineProvider for RustOutlineProvider {
     fn extract(path: &Path, src: &str) -> anyhow::Result<Vec<Symbol>> {
         let file: File =
             syn::parse_file(src).with_context(|| format!("failed to parse {:?}", path))?;
-        let mut v = Collector::default();
-        v.visit_file(&file);
-        Ok(v.symbols)
+        let mut collector = Collector {
+            local_types: local_type_names(&file.items),
+            ..Collector::default()
+        };
+        Ok(collector.collect(&file.items))
     }
 }
 
 /* ------------------------------------------------------------------ */
 
+/// モジュール階層をたどりながらシンボル木を組み立てる。
+///
+/// インライン `mod foo { ... }` の中身は `foo` の子になり、`path` は `foo::bar`。
+/// 関数本体内のアイテムは対象外。
 #[derive(Default)]
 struct Collector {
-    symbols: Vec<Symbol>,
+    /// 現在のモジュールパス (`a::b`)
+    module: Vec<String>,
+    /// ファイル内で定義された型の名前 (非公開も含む)
+    local_types: HashSet<String>,
+}
+
+impl Collector {
+    fn collect(&mut self, items: &[Item]) -> Vec<Symbol> {
+        let mut symbols = Vec::new();
+        // (self 型の名前, impl シンボル) – 最後に型の子へ付け替える
+        let mut impls: Vec<(String, Symbol)> = Vec::new();
+
+        for i in items {
+            let (kind, ident, vis) = match i {
+                Item::Mod(item) => {
+                    self.module.push(item.ident.to_string());
+                    let children = item
+                        .content
+                        .as_ref()
+                        .map(|(_, inner)| self.collect(inner))
+                        .unwrap_or_default();
+                    self.module.pop();
+                    // 非公開モジュールでも公開アイテムを含むなら残す
+                    if is_visible(&item.vis) || !children.is_empty() {
+                        let mut sym = self.symbol("mod", &item.ident, i);
+                        sym.children = children;
+                        symbols.push(sym);
+                    }
+                    continue;
+                }
+                Item::Impl(item) => {
+                    let ty = self_type_name(&item.self_ty);
+                    let local = ty.as_ref().is_some_and(|ty| self.local_types.contains(ty));
+                    // 外部の型 (`Vec<T>` / ブランケット `T` など) はトレイト impl だけ残す
+                    if !local && item.trait_.is_none() {
+                        continue;
+                    }
+                    if let Some(mut sym) = impl_symbol(item, &self.prefix(), ty.as_deref()) {
+                        match ty.filter(|_| local) {
+                            Some(ty) => impls.push((ty, sym)),
+                            None => {
+                                sym.kind = "foreign impl".into();
+                                symbols.push(sym);
+                            }
+                        }
+                    }
+                    continue;
+                }
+                Item::Struct(item) => ("struct", &item.ident, &item.vis),
+                Item::Enum(item) => ("enum", &item.ident, &item.vis),
+                Item::Trait(item) => ("trait", &item.ident, &item.vis),
+                Item::Fn(item) => ("fn", &item.sig.ident, &item.vis),
+                Item::Const(item) => ("const", &item.ident, &item.vis),
+                Item::Static(item) => ("static", &item.ident, &item.vis),
+                Item::Type(item) => ("type", &item.ident, &item.vis),
+                Item::Union(item) => ("union", &item.ident, &item.vis),
+                _ => continue,
+            };
+            if is_visible(vis) {
+                symbols.push(self.symbol(kind, ident, i));
+            }
+        }
+
+        attach_impls(symbols, impls)
+    }
+
+    fn symbol(&self, kind: &str, ident: &syn::Ident, item: &Item) -> Symbol {
+        Symbol {
+            kind: kind.into(),
+            ident: ident.to_string(),
+            path: format!("{}{}", self.prefix(), ident),
+            span: span_of(item),
+            signature: signature_of(item),
+            doc: doc_of(item_attrs(item)),
+            children: vec![],
+        }
+    }
+
+    /// `a::b::` (ルートなら空文字)
+    fn prefix(&self) -> String {
+        self.module.iter().map(|m| format!("{m}::")).collect()
+    }
+}
+
+fn is_visible(vis: &Visibility) -> bool {
+    matches!(vis, Visibility::Public(_) | Visibility::Restricted(_))
+}
+
+/// impl ブロックを同じモジュール内の型の子にぶら下げ、ソース順に並べ直す。
+/// 型が公開シンボルとして集められていなければ (非公開・別モジュール) 出力しない。
+fn attach_impls(mut symbols: Vec<Symbol>, impls: Vec<(String, Symbol)>) -> Vec<Symbol> {
+    for (ty, imp) in impls {
+        let owner = symbols
+            .iter_mut()
+            .find(|s| matches!(&s.kind[..], "struct" | "enum" | "union" | "type") && s.ident == ty);
+        if let Some(owner) = owner {
+            owner.children.push(imp);
+        }
+    }
+    symbols.sort_by_key(|s| s.span.map(|sp| (sp.start_line, sp.start_col)));
+    symbols
+}
+
+/* ---------- impl ブロック ---------- */
+
+/// `impl Trait for Type` / `impl Type` を 1 シンボルにまとめる。
+///
+/// 固有 impl は公開アイテムのみ、トレイト impl は全アイテムを子として持つ。
+/// 固有 impl で公開アイテムが無いものは出力しない。
+/// `prefix` はモジュールパス (`a::b::`)、`ty` は self 型の名前。
+fn impl_symbol(item: &ItemImpl, prefix: &str, ty: Option<&str>) -> Option<Symbol> {
+    let in_trait_impl = item.trait_.is_some();
+    // メソッドのパスは `a::b::Type::method`
+    let owner = match ty {
+        Some(ty) => format!("{prefix}{ty}::"),
+        None => prefix.to_string(),
+    };
+    let children: Vec<Symbol> = item
+        .items
+        .iter()
+        .filter_map(|ii| impl_item_symbol(ii, in_trait_impl, &owner))
+        .collect();
+    if !in_trait_impl && children.is_empty() {
+        return None;
+    }
+
+    // 見出し: ジェネリクス抜きの `Display for Foo<T>`
+    let mut bare = item.clone();
+    bare.attrs.clear();
+    bare.items.clear();
+    bare.generics = Default::default();
+    let ident = unparse_header(Item::Impl(bare));
+    let ident = ident.strip_prefix("impl ").unwrap_or(&ident).to_string();
+
+    // シグネチャ: ジェネリクス・where 句込みのヘッダ
+    let mut header = item.clone();
+    strip_docs(&mut header.attrs);
+    header.items.clear();
+
+    Some(Symbol {
+        kind: "impl".into(),
+        path: format!("{prefix}impl {ident}"),
+        ident,
+        span: span_of(item),
+        signature: Some(unparse_header(Item::Impl(header))),
+        doc: doc_of(&item.attrs),
+        children,
+    })
 }
 
-impl<'ast> Visit<'ast> for Collector {
-    fn visit_item(&mut self, i: &'ast Item) {
-        use Item::*;
+fn impl_item_symbol(ii: &ImplItem, in_trait_impl: bool, owner: &str) -> Option<Symbol> {
+    let visible = |vis: &Visibility| in_trait_impl || is_visible(vis);
+    // シグネチャ生成はトップレベルと共通化するため Item に詰め替える
+    let (kind, ident, as_item) = match ii {
+        ImplItem::Fn(f) if visible(&f.vis) => (
+            "fn",
+            &f.sig.ident,
+            Item::Fn(ItemFn {
+                attrs: f.attrs.clone(),
+                vis: f.vis.clone(),
+                sig: f.sig.clone(),
+                block: Box::new(syn::parse_quote!({})),
+            }),
+        ),
+        ImplItem::Const(c) if visible(&c.vis) => (
+            "const",
+            &c.ident,
+            Item::Const(ItemConst {
+                attrs: c.attrs.clone(),
+                vis: c.vis.clone(),
+                const_token: c.const_token,
+                ident: c.ident.clone(),
+                generics: c.generics.clone(),
+                colon_token: c.colon_token,
+                ty: Box::new(c.ty.clone()),
+                eq_token: c.eq_token,
+                expr: Box::new(syn::parse_quote!(_)),
+                semi_token: c.semi_token,
+            }),
+        ),
+        ImplItem::Type(t) if visible(&t.vis) => (
+            "type",
+            &t.ident,
+            Item::Type(ItemType {
+                attrs: t.attrs.clone(),
+                vis: t.vis.clone(),
+                type_token: t.type_token,
+                ident: t.ident.clone(),
+                generics: t.generics.clone(),
+                eq_token: t.eq_token,
+                ty: Box::new(t.ty.clone()),
+                semi_token: t.semi_token,
+            }),
+        ),
+        _ => return None,
+    };
+    Some(Symbol {
+        kind: kind.into(),
+        ident: ident.to_string(),
+        path: format!("{owner}{ident}"),
+        span: span_of(ii),
+        signature: signature_of(&as_item),
+        doc: doc_of(item_attrs(&as_item)),
+        children: vec![],
+    })
+}
 
-        // push() ヘルパ
-        let push = |kind: &str, ident: &syn::Ident, vis: &Visibility, symbols: &mut Vec<Symbol>| {
-            if matches!(vis, Visibility::Public(_) | Visibility::Restricted(_)) {
-                symbols.push(Symbol {
-                    kind: kind.into(),
-                    ident: ident.to_string(),
-                });
+/// ファイル内 (インラインモジュールも含む) で定義された型の名前
+fn local_type_names(items: &[Item]) -> HashSet<String> {
+    let mut names = HashSet::new();
+    for item in items {
+        match item {
+            Item::Struct(i) => names.insert(i.ident.to_string()),
+            Item::Enum(i) => names.insert(i.ident.to_string()),
+            Item::Union(i) => names.insert(i.ident.to_string()),
+            Item::Type(i) => names.insert(i.ident.to_string()),
+            Item::Mod(i) => {
+                if let Some((_, inner)) = &i.content {
+                    names.extend(local_type_names(inner));
+                }
+                continue;
             }
+            _ => continue,
         };
+    }
+    names
+}
+
+/// `Foo<T>` / `&Foo` / `path::Foo` → `Foo`
+fn self_type_name(ty: &Type) -> Option<String> {
+    match ty {
+        Type::Path(p) => p.path.segments.last().map(|s| s.ident.to_string()),
+        Type::Reference(r) => self_type_name(&r.elem),
+        Type::Paren(p) => self_type_name(&p.elem),
+        _ => None,
+    }
+}
+
+/// 中身を空にしたアイテムを整形し、末尾の `{}` を除いて返す
+fn unparse_header(item: Item) -> String {
+    let text = prettyplease::unparse(&File {
+        shebang: None,
+        attrs: vec![],
+        items: vec![item],
+    });
+    let text = text.trim_end();
+    text.strip_suffix("{}")
+        .unwrap_or(text)
+        .trim_end()
+        .to_string()
+}
+
+/// 外側属性 (`#[...]` / doc コメント) を除いたノードの範囲
+fn span_of<T: ToTokens>(node: &T) -> Option<Span> {
+    let tokens: Vec<TokenTree> = node.to_token_stream().into_iter().collect();
+    let mut first = 0;
+    while let (Some(TokenTree::Punct(p)), Some(TokenTree::Group(g))) =
+        (tokens.get(first), tokens.get(first + 1))
+    {
+        if p.as_char() != '#' || g.delimiter() != Delimiter::Bracket {
+            break;
+        }
+        first += 2;
+    }
+    let start = tokens.get(first)?.span().start();
+    let end = tokens.last()?.span().end();
+    Some(Span {
+        start_line: start.line,
+        start_col: start.column + 1,
+        end_line: end.line,
+        end_col: end.column,
+    })
+}
 
-        match i {
-            Mod(item) => push("mod", &item.ident, &item.vis, &mut self.symbols),
-            Struct(item) => push("struct", &item.ident, &item.vis, &mut self.symbols),
-            Enum(item) => push("enum", &item.ident, &item.vis, &mut self.symbols),
-            Trait(item) => push("trait", &item.ident, &item.vis, &mut self.symbols),
-            Fn(item) => push("fn", &item.sig.ident, &item.vis, &mut self.symbols),
-            Const(item) => push("const", &item.ident, &item.vis, &mut self.symbols),
-            Static(item) => push("static", &item.ident, &item.vis, &mut self.symbols),
-            Type(item) => push("type", &item.ident, &item.vis, &mut self.symbols),
-            _ => {}
+/// 本体を省いたアイテムのシグネチャ (prettyplease で整形)
+///
+/// - fn: 引数・戻り値・ジェネリクス・where 句まで (本体 `{}` は除去)
+/// - struct / enum: フィールド・バリアントを含む定義全体
+/// - trait: メソッドはシグネチャのみ (デフォルト実装は除去)
+/// - const / static: 型まで (値は除去)
+///
+/// doc コメントは除去し、`#[derive]` などその他の属性は残す。
+fn signature_of(item: &Item) -> Option<String> {
+    let mut item = item.clone();
+    let mut elide_value = false;
+    match &mut item {
+        Item::Fn(f) => {
+            strip_docs(&mut f.attrs);
+            *f.block = syn::parse_quote!({});
+        }
+        Item::Struct(s) => {
+            strip_docs(&mut s.attrs);
+            s.fields.iter_mut().for_each(|f| strip_docs(&mut f.attrs));
+        }
+        Item::Enum(e) => {
+            strip_docs(&mut e.attrs);
+            for v in e.variants.iter_mut() {
+                strip_docs(&mut v.attrs);
+                v.fields.iter_mut().for_each(|f| strip_docs(&mut f.attrs));
+            }
+        }
+        Item::Trait(t) => {
+            strip_docs(&mut t.attrs);
+            for ti in t.items.iter_mut() {
+                match ti {
+                    TraitItem::Fn(f) => {
+                        strip_docs(&mut f.attrs);
+                        f.default = None;
+                        f.semi_token = Some(Default::default());
+                    }
+                    TraitItem::Const(c) => {
+                        strip_docs(&mut c.attrs);
+                        c.default = None;
+                    }
+                    TraitItem::Type(ty) => {
+                        strip_docs(&mut ty.attrs);
+                        ty.default = None;
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Item::Const(c) => {
+            strip_docs(&mut c.attrs);
+            *c.expr = syn::parse_quote!(_);
+            elide_value = true;
         }
+        Item::Static(s) => {
+            strip_docs(&mut s.attrs);
+            *s.expr = syn::parse_quote!(_);
+            elide_value = true;
+        }
+        Item::Type(t) => strip_docs(&mut t.attrs),
+        Item::Mod(m) => {
+            strip_docs(&mut m.attrs);
+            m.content = None;
+            m.semi = Some(Default::default());
+        }
+        _ => return None,
+    }
 
-        // 再帰
-        syn::visit::visit_item(self, i);
+    let text = unparse_header(item);
+    let text = match text.strip_suffix(" = _;") {
+        Some(head) if elide_value => format!("{head};"),
+        _ => text,
+    };
+    Some(text)
+}
+
+/// `#[doc = ".."]` (`///` / `//!` を含む) を連結した doc コメント本文
+///
+/// 各行の先頭 1 スペース (`/// foo` の区切り) を外し、前後の空行を落とす。
+fn doc_of(attrs: &[Attribute]) -> Option<String> {
+    let lines: Vec<String> = attrs
+        .iter()
+        .filter(|a| a.path().is_ident("doc"))
+        .filter_map(|a| match &a.meta {
+            Meta::NameValue(nv) => match &nv.value {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(s), ..
+                }) => Some(s.value()),
+                _ => None,
+            },
+            _ => None,
+        })
+        // `/** .. */` は 1 属性に複数行が入る
+        .flat_map(|s| s.split('\n').map(str::to_string).collect::<Vec<_>>())
+        .map(|l| l.strip_prefix(' ').unwrap_or(&l).trim_end().to_string())
+        .collect();
+    let text = lines.join("\n");
+    let text = text.trim_matches('\n');
+    (!text.trim().is_empty()).then(|| text.to_string())
+}
+
+fn item_attrs(item: &Item) -> &[Attribute] {
+    match item {
+        Item::Mod(i) => &i.attrs,
+        Item::Struct(i) => &i.attrs,
+        Item::Enum(i) => &i.attrs,
+        Item::Trait(i) => &i.attrs,
+        Item::Fn(i) => &i.attrs,
+        Item::Const(i) => &i.attrs,
+        Item::Static(i) => &i.attrs,
+        Item::Type(i) => &i.attrs,
+        Item::Union(i) => &i.attrs,
+        Item::Impl(i) => &i.attrs,
+        _ => &[],
     }
 }
 
+fn strip_docs(attrs: &mut Vec<Attribute>) {
+    attrs.retain(|a| !a.path().is_ident("doc"));
+}
+
 /* ------------------------------------------------------------------ */
 /* tests                                                              */
 /* ------------------------------------------------------------------ */
@@ -79,4 +451,147 @@ mod tests {
         let kinds: Vec<_> = syms.iter().map(|s| (&s.kind[..], &s.ident[..])).collect();
         assert_eq!(kinds, vec![("struct", "PubSt"), ("fn", "inner")]);
     }
+
+    #[test]
+    fn inline_modules_become_subtrees() {
+        let src = r#"
+            mod foo {
+                pub fn bar() {}
+                pub mod baz {
+                    pub struct Qux;
+                    impl Qux { pub fn new() -> Self { Qux } }
+                }
+                fn hidden() {}
+            }
+            mod empty { fn nothing() {} }
+            pub fn top() { pub fn in_body() {} }
+        "#;
+        let syms = RustOutlineProvider::extract(Path::new("dummy.rs"), src).unwrap();
+        let roots: Vec<_> = syms.iter().map(|s| &s.ident[..]).collect();
+        assert_eq!(roots, vec!["foo", "top"]);
+
+        let paths: Vec<_> = Symbol::flatten(syms).into_iter().map(|s| s.path).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "foo",
+                "foo::bar",
+                "foo::baz",
+                "foo::baz::Qux",
+                "foo::baz::impl Qux",
+                "foo::baz::Qux::new",
+                "top",
+            ]
+        );
+    }
+
+    #[test]
+    fn signatures_elide_bodies() {
+        let src = r#"
+            /// doc
+            pub fn get<T: Clone>(x: &[T], i: usize) -> Option<T>
+            where
+                T: Default,
+            {
+                x.get(i).cloned()
+            }
+            #[derive(Debug)]
+            pub struct Pt { pub x: i32, y: i32 }
+            pub trait Shape { fn area(&self) -> f64 { 0.0 } }
+            pub const MAX: usize = 10 * 1024;
+        "#;
+        let syms = RustOutlineProvider::extract(Path::new("dummy.rs"), src).unwrap();
+        let sigs: Vec<_> = syms.iter().map(|s| s.signature.clone().unwrap()).collect();
+        assert_eq!(
+            sigs[0],
+            "pub fn get<T: Clone>(x: &[T], i: usize) -> Option<T>\nwhere\n    T: Default,"
+        );
+        assert_eq!(
+            sigs[1],
+            "#[derive(Debug)]\npub struct Pt {\n    pub x: i32,\n    y: i32,\n}"
+        );
+        assert_eq!(sigs[2], "pub trait Shape {\n    fn area(&self) -> f64;\n}");
+        assert_eq!(sigs[3], "pub const MAX: usize;");
+    }
+
+    #[test]
+    fn impl_blocks_nest_under_their_type() {
+        let src = r#"
+            pub struct Foo;
+            impl Foo {
+                pub fn new() -> Self { Foo }
+                fn private(&self) {}
+                pub const ID: u32 = 1;
+            }
+            impl std::fmt::Display for Foo {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+            }
+            impl Foo { fn hidden() {} }
+            impl<T: Clone> From<T> for Bar<T> { fn from(_: T) -> Self { todo!() } }
+            impl Bar<u8> { pub fn inherent() {} }
+            struct Private;
+            impl Private { pub fn new() -> Self { Private } }
+            impl Clone for Private { fn clone(&self) -> Self { Private } }
+        "#;
+        let syms = RustOutlineProvider::extract(Path::new("dummy.rs"), src).unwrap();
+        assert_eq!(syms.len(), 2, "Foo + trait impl for foreign Bar");
+
+        let foo = &syms[0];
+        let impls: Vec<_> = foo.children.iter().map(|c| &c.ident[..]).collect();
+        assert_eq!(impls, vec!["Foo", "std::fmt::Display for Foo"]);
+        let methods: Vec<_> = foo.children[0]
+            .children
+            .iter()
+            .map(|c| (&c.kind[..], &c.ident[..]))
+            .collect();
+        assert_eq!(methods, vec![("fn", "new"), ("const", "ID")]);
+        assert_eq!(foo.children[1].children[0].ident, "fmt");
+
+        assert_eq!(syms[1].kind, "foreign impl");
+        assert_eq!(syms[1].ident, "From<T> for Bar<T>");
+        assert_eq!(
+            syms[1].signature.as_deref(),
+            Some("impl<T: Clone> From<T> for Bar<T>")
+        );
+    }
+
+    #[test]
+    fn doc_comments_are_captured() {
+        let src = r#"
+            /// Opens a connection.
+            ///
+            /// Retries up to three times.
+            pub fn connect() {}
+            #[doc = "Raw attribute doc."]
+            pub struct Conn;
+            impl Conn {
+                /** Closes it. */
+                pub fn close(&self) {}
+            }
+            pub fn undocumented() {}
+        "#;
+        let syms =
+            Symbol::flatten(RustOutlineProvider::extract(Path::new("dummy.rs"), src).unwrap());
+        let docs: Vec<_> = syms.iter().map(|s| s.doc.as_deref()).collect();
+        assert_eq!(
+            docs,
+            vec![
+                Some("Opens a connection.\n\nRetries up to three times."),
+                Some("Raw attribute doc."),
+                None,
+                Some("Closes it."),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn spans_cover_item_without_attributes() {
+        let src = "/// doc\n#[inline]\npub fn foo() {\n    1;\n}\n";
+        let syms = RustOutlineProvider::extract(Path::new("dummy.rs"), src).unwrap();
+        let span = syms[0].span.unwrap();
+        assert_eq!((span.start_line, span.start_col), (3, 1));
+        assert_eq!((span.end_line, span.end_col), (5, 1));
+        assert_eq!(span.lines_label(), "L3-L5");
+    }
 }
@@ -0,0 +1,209 @@
+// src/scanner/budget.rs
+//
+// トークン予算 (`max_tokens`) に収まるよう、ファイルを段階的に縮退させる。
+//
+//   full → outline (対応プロバイダがある場合のみ) → tree-only
+//
+// 縮退は優先度の低いファイル (`[priority]` に一致しないもの → 後ろのパターン) から、
+// 同じ優先度ならトークン数の多いものから順に行う。
+// まず全候補を outline 化し、それでも超える場合に tree-only へ落とす。
+
+use super::format::FileMeta;
+use super::OmitReason;
+use globset::GlobSet;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// 出力する 1 ファイル分のブロック
+pub struct Block {
+    pub rel: PathBuf,
+    pub path: PathBuf,
+    /// 見出し・囲みを除いた中身 (本文またはアウトライン)
+    pub body: Vec<u8>,
+    /// 見出し・囲みを含めたトークン数
+    pub tokens: usize,
+    /// すでにアウトライン (`[outline]` または予算による縮退)
+    pub outlined: bool,
+    pub meta: FileMeta,
+}
+
+/// 縮退の結果
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Degraded {
+    pub outlined: usize,
+    pub dropped: usize,
+}
+
+/// `fixed` (ツリーなどブロック以外) + 各ブロックが `limit` に収まるまで縮退させる。
+///
+/// `fixed` は全ブロックに予算の注記が付いた最悪の場合のツリーで見積もっておく
+/// (縮退で注記が増えても上限を超えないように)。
+/// `outline` は対応言語ならアウトラインに差し替えたブロックを返す。
+/// 縮退したファイルは `omitted` に `Budget*` として記録され、ツリーで注記される。
+pub fn fit(
+    blocks: &mut Vec<Block>,
+    omitted: &mut HashMap<PathBuf, OmitReason>,
+    limit: usize,
+    fixed: usize,
+    priority: &[GlobSet],
+    outline: impl Fn(&Block) -> Option<Block>,
+) -> Degraded {
+    let mut total = fixed + blocks.iter().map(|b| b.tokens).sum::<usize>();
+    if total <= limit {
+        return Degraded::default();
+    }
+
+    let mut order: Vec<usize> = (0..blocks.len()).collect();
+    order.sort_by(|&a, &b| {
+        let (a, b) = (&blocks[a], &blocks[b]);
+        rank(&b.rel, priority)
+            .cmp(&rank(&a.rel, priority))
+            .then(b.tokens.cmp(&a.tokens))
+            .then_with(|| a.rel.cmp(&b.rel))
+    });
+
+    /* --- full → outline ------------------------------------------- */
+    let mut outlined = HashSet::new();
+    for &i in &order {
+        if total <= limit {
+            break;
+        }
+        let block = &mut blocks[i];
+        if block.outlined {
+            continue;
+        }
+        let Some(replaced) = outline(block) else {
+            continue;
+        };
+        if replaced.tokens >= block.tokens {
+            continue; // 縮まないなら本文のまま
+        }
+        total = total - block.tokens + replaced.tokens;
+        *block = Block {
+            outlined: true,
+            ..replaced
+        };
+        omitted.insert(block.rel.clone(), OmitReason::BudgetOutline);
+        outlined.insert(i);
+    }
+
+    /* --- outline / full → tree-only ------------------------------- */
+    let mut dropped = HashSet::new();
+    for &i in &order {
+        if total <= limit {
+            break;
+        }
+        let block = &blocks[i];
+        total -= block.tokens;
+        omitted.insert(block.rel.clone(), OmitReason::Budget);
+        outlined.remove(&i);
+        dropped.insert(i);
+    }
+
+    let mut idx = 0;
+    blocks.retain(|_| {
+        idx += 1;
+        !dropped.contains(&(idx - 1))
+    });
+    Degraded {
+        outlined: outlined.len(),
+        dropped: dropped.len(),
+    }
+}
+
+/// `[priority]` の何番目のパターンに一致するか (一致なしは最下位)
+fn rank(rel: &Path, priority: &[GlobSet]) -> usize {
+    priority
+        .iter()
+        .position(|gs| gs.is_match(rel))
+        .unwrap_or(priority.len())
+}
+
+/* --------------------------------------------------------------------- */
+/* tests                                                                 */
+/* --------------------------------------------------------------------- */
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::utils::build_globset;
+
+    /// chars/4 で `n` トークンになる本文
+    fn block(rel: &str, n: usize) -> Block {
+        Block {
+            rel: rel.into(),
+            path: rel.into(),
+            body: "abcd".repeat(n).into_bytes(),
+            tokens: n,
+            outlined: false,
+            meta: FileMeta::default(),
+        }
+    }
+
+    fn outline_rs(b: &Block) -> Option<Block> {
+        (b.rel.extension()? == "rs").then(|| Block {
+            body: b"outline!".to_vec(),
+            ..block(b.rel.to_str().unwrap(), 2)
+        })
+    }
+
+    fn run(blocks: &mut Vec<Block>, limit: usize, priority: &[&str]) -> Degraded {
+        let priority: Vec<_> = priority
+            .iter()
+            .filter_map(|p| build_globset(&[p.to_string()]))
+            .collect();
+        let mut omitted = HashMap::new();
+        let d = fit(blocks, &mut omitted, limit, 0, &priority, outline_rs);
+        for b in blocks.iter() {
+            assert_eq!(
+                omitted.contains_key(&b.rel),
+                b.outlined,
+                "kept blocks are annotated only when outlined"
+            );
+        }
+        d
+    }
+
+    #[test]
+    fn within_budget_is_untouched() {
+        let mut blocks = vec![block("a.rs", 10), block("b.rs", 10)];
+        assert_eq!(run(&mut blocks, 20, &[]), Degraded::default());
+        assert!(blocks.iter().all(|b| !b.outlined));
+    }
+
+    #[test]
+    fn low_priority_degrades_first_then_drops() {
+        let mut blocks = vec![
+            block("src/lib.rs", 50),
+            block("src/big.rs", 100),
+            block("notes.txt", 30),
+        ];
+        // big.rs を outline 化すれば 50 + 2 + 30 に収まる
+        let d = run(&mut blocks, 100, &["src/lib.rs"]);
+        assert_eq!(
+            d,
+            Degraded {
+                outlined: 1,
+                dropped: 0
+            }
+        );
+        assert!(blocks[1].outlined && !blocks[0].outlined);
+
+        // さらに厳しいと、全員 outline 化した後に優先度の低い順から落ちる
+        let mut blocks = vec![
+            block("src/lib.rs", 50),
+            block("src/big.rs", 100),
+            block("notes.txt", 30),
+        ];
+        let d = run(&mut blocks, 25, &["src/lib.rs"]);
+        assert_eq!(
+            d,
+            Degraded {
+                outlined: 1,
+                dropped: 2
+            }
+        );
+        let kept: Vec<_> = blocks.iter().map(|b| b.rel.to_str().unwrap()).collect();
+        assert_eq!(kept, vec!["src/lib.rs"]);
+        assert!(blocks[0].outlined);
+    }
+}
@@ -7,6 +7,13 @@ pub struct ProcessCounter {
     skipped_by_pattern: usize,
     skipped_binary: usize,
     skipped_size: usize,
+    total_tokens: usize,
+    token_method: &'static str,
+    /// (相対パス, トークン数)
+    file_tokens: Vec<(String, usize)>,
+    top_n: usize,
+    /// (上限, アウトライン化, ツリーのみ)
+    budget: Option<(usize, usize, usize)>,
 }
 
 impl ProcessCounter {
@@ -34,6 +41,61 @@ impl ProcessCounter {
         self.skipped_size += 1;
     }
 
+    /// 出力全体のトークン数と見積もり方式
+    pub fn set_tokens(&mut self, total: usize, method: &'static str) {
+        self.total_tokens = total;
+        self.token_method = method;
+    }
+
+    pub fn record_file_tokens(&mut self, path: String, tokens: usize) {
+        self.file_tokens.push((path, tokens));
+    }
+
+    /// `max_tokens` による縮退の結果
+    pub fn set_budget(&mut self, limit: usize, outlined: usize, dropped: usize) {
+        self.budget = Some((limit, outlined, dropped));
+    }
+
+    /// サマリーに出す重いファイルの件数 (0 で非表示)
+    pub fn set_top_n(&mut self, n: usize) {
+        self.top_n = n;
+    }
+
+    /// トークン数の多い順に上位 `top_n` 件 (同数ならパス順)
+    fn heaviest(&self) -> Vec<&(String, usize)> {
+        let mut files: Vec<_> = self.file_tokens.iter().collect();
+        files.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        files.truncate(self.top_n);
+        files
+    }
+
+    /// 構造化出力 (json / jsonl) のヘッダに載せるサマリー
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "files": {
+                "total": self.total_files,
+                "processed": self.processed_files,
+                "skipped_pattern": self.skipped_by_pattern,
+                "skipped_binary": self.skipped_binary,
+                "skipped_size": self.skipped_size,
+            },
+            "tokens": {
+                "total": self.total_tokens,
+                "method": self.token_method,
+            },
+            "budget": self.budget.map(|(limit, outlined, dropped)| serde_json::json!({
+                "limit": limit,
+                "outlined": outlined,
+                "dropped": dropped,
+            })),
+            "heaviest": self
+                .heaviest()
+                .iter()
+                .map(|(path, tokens)| serde_json::json!({ "path": path, "tokens": tokens }))
+                .collect::<Vec<_>>(),
+        })
+    }
+
     pub fn print_summary(&self) {
         let total_skipped = self.skipped_by_pattern + self.skipped_binary + self.skipped_size;
 
@@ -58,6 +120,20 @@ impl ProcessCounter {
                 eprintln!("  - Size limit exceeded: {} files", self.skipped_size);
             }
         }
+
+        if !self.token_method.is_empty() {
+            eprintln!("- Tokens: {} ({})", self.total_tokens, self.token_method);
+            if let Some((limit, outlined, dropped)) = self.budget {
+                eprintln!("- Token budget: {limit} ({outlined} outlined, {dropped} tree-only)");
+            }
+            let heaviest = self.heaviest();
+            if !heaviest.is_empty() {
+                eprintln!("- Heaviest files:");
+                for (path, tokens) in heaviest {
+                    eprintln!("  - {path}: {tokens} tokens");
+                }
+            }
+        }
     }
 }
 
@@ -89,4 +165,18 @@ mod tests {
         // print_summary が panic しないことだけ確認
         c.print_summary();
     }
+
+    #[test]
+    fn heaviest_files_are_sorted_and_truncated() {
+        let mut c = ProcessCounter::new();
+        c.set_top_n(2);
+        c.set_tokens(60, "estimated, chars/4");
+        c.record_file_tokens("a.rs".into(), 10);
+        c.record_file_tokens("b.rs".into(), 30);
+        c.record_file_tokens("c.rs".into(), 20);
+
+        let top: Vec<_> = c.heaviest().into_iter().map(|(p, _)| &p[..]).collect();
+        assert_eq!(top, vec!["b.rs", "c.rs"]);
+        c.print_summary();
+    }
 }
@@ -0,0 +1,176 @@
+// src/scanner/format/json.rs
+//
+// ツール連携向けの構造化出力
+//
+// JSON  : { "header": {…}, "files": [ {…}, … ] }
+// JSONL : 1 行目が {"type":"header",…}、以降 1 行 1 ファイルの {"type":"file",…}
+//
+// ファイルのオブジェクトは path / size / language / omitted / truncated / lines と、
+// 本文なら content、アウトラインなら symbols を持つ。
+
+use super::{FileDoc, Header, Renderer};
+use serde_json::{json, Map, Value};
+use std::io::Write;
+
+pub struct Json {
+    /// JSONL (1 行 1 オブジェクト)
+    pub lines: bool,
+}
+
+impl Json {
+    fn header_value(header: &Header<'_>) -> Map<String, Value> {
+        let mut h = Map::new();
+        if let Some((k, total)) = header.part {
+            h.insert("part".into(), json!({ "index": k, "total": total }));
+        }
+        if let Some(tree) = header.tree {
+            h.insert("tree".into(), json!(tree));
+        }
+        if let Some(files) = header.files {
+            h.insert("files".into(), json!(files));
+        }
+        if let Some(summary) = header.summary {
+            h.insert("summary".into(), summary.clone());
+        }
+        h
+    }
+
+    fn file_value(doc: &FileDoc<'_>) -> Map<String, Value> {
+        let meta = doc.meta;
+        let mut f = Map::new();
+        f.insert("index".into(), json!(doc.index));
+        f.insert("path".into(), json!(doc.rel));
+        f.insert("size".into(), json!(meta.size));
+        f.insert("language".into(), json!(meta.language));
+        f.insert("omitted".into(), json!(doc.omitted));
+        f.insert("truncated".into(), json!(meta.truncated));
+        f.insert("lines".into(), json!(meta.lines));
+        match &meta.symbols {
+            Some(symbols) => {
+                f.insert("symbols".into(), json!(symbols));
+            }
+            None => {
+                let content = doc.content.map(|c| String::from_utf8_lossy(c).into_owned());
+                f.insert("content".into(), json!(content));
+            }
+        }
+        f
+    }
+}
+
+impl Renderer for Json {
+    fn header(&self, out: &mut Vec<u8>, header: &Header<'_>) {
+        let mut h = Self::header_value(header);
+        if self.lines {
+            h.insert("type".into(), json!("header"));
+            writeln!(out, "{}", Value::Object(h)).ok();
+        } else {
+            writeln!(out, "{{\"header\": {},", Value::Object(h)).ok();
+        }
+    }
+
+    fn begin(&self, out: &mut Vec<u8>) {
+        if !self.lines {
+            writeln!(out, "\"files\": [").ok();
+        }
+    }
+
+    fn end(&self, out: &mut Vec<u8>) {
+        if !self.lines {
+            writeln!(out, "\n]}}").ok();
+        }
+    }
+
+    fn separator(&self) -> &'static [u8] {
+        if self.lines {
+            b""
+        } else {
+            b",\n"
+        }
+    }
+
+    fn file(&self, out: &mut Vec<u8>, doc: &FileDoc<'_>) {
+        let mut f = Self::file_value(doc);
+        if self.lines {
+            f.insert("type".into(), json!("file"));
+            writeln!(out, "{}", Value::Object(f)).ok();
+        } else {
+            write!(out, "{}", Value::Object(f)).ok();
+        }
+    }
+}
+
+/* --------------------------------------------------------------------- */
+/* tests                                                                 */
+/* --------------------------------------------------------------------- */
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::format::FileMeta;
+
+    fn render(r: &Json) -> String {
+        let meta = FileMeta {
+            size: Some(6),
+            language: Some("rust".into()),
+            lines: Some(1),
+            ..FileMeta::default()
+        };
+        let skipped = FileMeta::default();
+        let docs = [
+            FileDoc {
+                index: 1,
+                rel: "a.rs",
+                content: Some(b"fn a()"),
+                meta: &meta,
+                omitted: None,
+            },
+            FileDoc {
+                index: 2,
+                rel: "b.bin",
+                content: None,
+                meta: &skipped,
+                omitted: Some("binary".into()),
+            },
+        ];
+        let mut out = Vec::new();
+        r.header(
+            &mut out,
+            &Header {
+                tree: Some("a.rs\nb.bin\n"),
+                ..Header::default()
+            },
+        );
+        r.begin(&mut out);
+        for (i, doc) in docs.iter().enumerate() {
+            if i > 0 {
+                out.extend_from_slice(r.separator());
+            }
+            r.file(&mut out, doc);
+        }
+        r.end(&mut out);
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn json_is_a_single_document() {
+        let v: Value = serde_json::from_str(&render(&Json { lines: false })).unwrap();
+        assert_eq!(v["header"]["tree"], "a.rs\nb.bin\n");
+        assert_eq!(v["files"][0]["content"], "fn a()");
+        assert_eq!(v["files"][0]["language"], "rust");
+        assert_eq!(v["files"][1]["omitted"], "binary");
+        assert!(v["files"][1]["content"].is_null());
+    }
+
+    #[test]
+    fn jsonl_has_one_object_per_line() {
+        let out = render(&Json { lines: true });
+        let lines: Vec<Value> = out
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0]["type"], "header");
+        assert_eq!(lines[1]["type"], "file");
+        assert_eq!(lines[2]["path"], "b.bin");
+    }
+}
@@ -0,0 +1,99 @@
+// src/scanner/format/markdown.rs
+//
+// 従来の Markdown 風出力
+//
+// 中身にバッククォートの連続 (```) が含まれていても閉じてしまわないよう、
+// フェンスはその最長の連続より 1 つ長くする。
+// 言語が分かれば開きフェンスに info string (```rust など) を付ける。
+
+use super::{FileDoc, Header, Renderer};
+use std::io::Write;
+
+pub struct Markdown;
+
+impl Renderer for Markdown {
+    fn header(&self, out: &mut Vec<u8>, header: &Header<'_>) {
+        if let Some((k, total)) = header.part {
+            writeln!(out, "## Part {k}/{total}").ok();
+            writeln!(out).ok();
+        }
+        if let Some(files) = header.files {
+            writeln!(out, "Files in this part:").ok();
+            for f in files {
+                writeln!(out, "- {f}").ok();
+            }
+            writeln!(out).ok();
+        }
+        if let Some(tree) = header.tree {
+            let fence = fence(tree.as_bytes());
+            writeln!(out, "{fence}").ok();
+            out.extend_from_slice(tree.as_bytes());
+            writeln!(out, "{fence}").ok();
+            writeln!(out).ok();
+        }
+    }
+
+    fn file(&self, out: &mut Vec<u8>, doc: &FileDoc<'_>) {
+        let Some(content) = doc.content else {
+            return; // ツリーのみ
+        };
+        let fence = fence(content);
+        // アウトラインは元の言語ではないので info string なし
+        let lang = match &doc.meta.language {
+            Some(lang) if !doc.meta.outline => lang.as_str(),
+            _ => "",
+        };
+        writeln!(out, "### {}", doc.rel).ok();
+        writeln!(out, "{fence}{lang}").ok();
+        out.extend_from_slice(content);
+        writeln!(out, "{fence}").ok();
+        writeln!(out).ok();
+    }
+}
+
+/// `text` 中のどのバッククォート列よりも長いフェンス (最低 3 つ)
+fn fence(text: &[u8]) -> String {
+    let longest = text
+        .split(|&b| b != b'`')
+        .map(<[u8]>::len)
+        .max()
+        .unwrap_or(0);
+    "`".repeat((longest + 1).max(3))
+}
+
+/* --------------------------------------------------------------------- */
+/* tests                                                                 */
+/* --------------------------------------------------------------------- */
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::format::FileMeta;
+
+    #[test]
+    fn fence_outgrows_longest_backtick_run() {
+        assert_eq!(fence(b"plain"), "```");
+        assert_eq!(fence(b"`code` and ``x``"), "```");
+        assert_eq!(fence(b"/// ```\n/// let x = 1;\n/// ```\n"), "````");
+        assert_eq!(fence(b"`````"), "``````");
+    }
+
+    #[test]
+    fn file_with_fences_stays_closed() {
+        let meta = FileMeta::default();
+        let mut out = Vec::new();
+        Markdown.file(
+            &mut out,
+            &FileDoc {
+                index: 1,
+                rel: "README.md",
+                content: Some(b"```rust\nfn main() {}\n```\n"),
+                meta: &meta,
+                omitted: None,
+            },
+        );
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "### README.md\n````\n```rust\nfn main() {}\n```\n````\n\n"
+        );
+    }
+}
@@ -0,0 +1,91 @@
+// src/scanner/format/mod.rs
+//
+// gather モードの出力形式
+//
+//   Markdown : ```ツリー``` → ### path + フェンス (既定)
+//   Xml      : <directory_tree> → <documents><document index="n">…
+//   Json     : { "header": {…}, "files": [ {…}, … ] }
+//   Jsonl    : {"type":"header",…} の後に 1 行 1 ファイル
+//
+// スキャナは各ファイルの中身 (本文 / アウトライン) とメタ情報だけを作り、
+// 見出しや囲みはすべて `Renderer` が書く。
+
+mod json;
+mod markdown;
+mod xml;
+
+use crate::model::OutputFormat;
+use crate::outline::Symbol;
+
+/// 1 ファイル分のメタ情報
+#[derive(Debug, Default)]
+pub struct FileMeta {
+    /// ファイルサイズ (bytes)
+    pub size: Option<u64>,
+    /// 言語名 (Markdown ではフェンスの info string)
+    pub language: Option<String>,
+    /// 元ファイルの行数
+    pub lines: Option<usize>,
+    /// `max_lines` で打ち切った
+    pub truncated: bool,
+    /// 中身が本文ではなくアウトライン
+    pub outline: bool,
+    /// アウトラインとして出力したときのシンボル
+    pub symbols: Option<Vec<Symbol>>,
+}
+
+/// 1 ファイル分の出力内容
+pub struct FileDoc<'a> {
+    /// 1 始まりの通し番号
+    pub index: usize,
+    pub rel: &'a str,
+    /// 本文 / アウトライン (ツリーのみのファイルは `None`)
+    pub content: Option<&'a [u8]>,
+    pub meta: &'a FileMeta,
+    /// 省略・縮退の理由 (ツリーの `[omitted:…]` と同じ表記)
+    pub omitted: Option<String>,
+}
+
+/// 出力 (または分割した各パート) の先頭
+#[derive(Default)]
+pub struct Header<'a> {
+    /// 分割時の (k, N)
+    pub part: Option<(usize, usize)>,
+    /// ディレクトリツリー (1 行 1 エントリ)
+    pub tree: Option<&'a str>,
+    /// ツリーの代わりに置く、そのパートのファイル一覧 (`split_header = index`)
+    pub files: Option<&'a [&'a str]>,
+    /// 処理結果のサマリー
+    pub summary: Option<&'a serde_json::Value>,
+}
+
+/// 出力形式ごとの書き出し
+///
+/// 書き出し順は `header` → `begin` → `file` (間に `separator`) → `end`。
+pub trait Renderer {
+    fn header(&self, out: &mut Vec<u8>, header: &Header<'_>);
+
+    /// ファイル群の前
+    fn begin(&self, _out: &mut Vec<u8>) {}
+
+    /// ファイル群の後
+    fn end(&self, _out: &mut Vec<u8>) {}
+
+    /// ファイル同士の区切り
+    fn separator(&self) -> &'static [u8] {
+        b""
+    }
+
+    /// 1 ファイル分
+    fn file(&self, out: &mut Vec<u8>, doc: &FileDoc<'_>);
+}
+
+/// `OutputFormat` に対応するレンダラ
+pub fn renderer(fmt: OutputFormat) -> Box<dyn Renderer> {
+    match fmt {
+        OutputFormat::Markdown => Box::new(markdown::Markdown),
+        OutputFormat::Xml => Box::new(xml::Xml),
+        OutputFormat::Json => Box::new(json::Json { lines: false }),
+        OutputFormat::Jsonl => Box::new(json::Json { lines: true }),
+    }
+}
@@ -0,0 +1,115 @@
+// src/scanner/format/xml.rs
+//
+// Claude 向けプロンプトでよく使われる XML 形式
+//
+// ```xml
+// <documents>
+// <document index="1">
+// <source>src/lib.rs</source>
+// <document_content>
+// …
+// </document_content>
+// </document>
+// </documents>
+// ```
+
+use super::{FileDoc, Header, Renderer};
+use std::io::Write;
+
+pub struct Xml;
+
+impl Renderer for Xml {
+    fn header(&self, out: &mut Vec<u8>, header: &Header<'_>) {
+        if let Some((k, total)) = header.part {
+            writeln!(out, "<!-- Part {k}/{total} -->").ok();
+        }
+        if let Some(files) = header.files {
+            writeln!(out, "<part_files>").ok();
+            for f in files {
+                writeln!(out, "<source>{}</source>", escape(f)).ok();
+            }
+            writeln!(out, "</part_files>").ok();
+        }
+        if let Some(tree) = header.tree {
+            writeln!(out, "<directory_tree>").ok();
+            out.extend_from_slice(escape(tree).as_bytes());
+            writeln!(out, "</directory_tree>").ok();
+        }
+    }
+
+    fn begin(&self, out: &mut Vec<u8>) {
+        writeln!(out, "<documents>").ok();
+    }
+
+    fn end(&self, out: &mut Vec<u8>) {
+        writeln!(out, "</documents>").ok();
+    }
+
+    fn file(&self, out: &mut Vec<u8>, doc: &FileDoc<'_>) {
+        let Some(content) = doc.content else {
+            return; // ツリーのみ
+        };
+        writeln!(out, "<document index=\"{}\">", doc.index).ok();
+        writeln!(out, "<source>{}</source>", escape(doc.rel)).ok();
+        writeln!(out, "<document_content>").ok();
+        out.extend_from_slice(escape(&String::from_utf8_lossy(content)).as_bytes());
+        writeln!(out, "</document_content>").ok();
+        writeln!(out, "</document>").ok();
+    }
+}
+
+/// テキストノード / 属性値として安全な形にする
+fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            // XML 1.0 で使えない制御文字は落とす
+            c if c.is_control() && !matches!(c, '\t' | '\n' | '\r') => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/* --------------------------------------------------------------------- */
+/* tests                                                                 */
+/* --------------------------------------------------------------------- */
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::format::FileMeta;
+
+    #[test]
+    fn escapes_markup_and_control_chars() {
+        assert_eq!(
+            escape("a < b && \"c\" > 'd'\u{0}\n"),
+            "a &lt; b &amp;&amp; &quot;c&quot; &gt; &apos;d&apos;\n"
+        );
+    }
+
+    #[test]
+    fn document_layout() {
+        let mut out = Vec::new();
+        let meta = FileMeta::default();
+        Xml.file(
+            &mut out,
+            &FileDoc {
+                index: 3,
+                rel: "src/<a>.rs",
+                content: Some(b"fn f() -> Vec<u8> {}\n"),
+                meta: &meta,
+                omitted: None,
+            },
+        );
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "<document index=\"3\">\n<source>src/&lt;a&gt;.rs</source>\n<document_content>\n\
+             fn f() -&gt; Vec&lt;u8&gt; {}\n</document_content>\n</document>\n"
+        );
+    }
+}
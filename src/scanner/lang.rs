@@ -0,0 +1,144 @@
+// src/scanner/lang.rs
+//
+// 拡張子 / よく知られたファイル名から言語名 (フェンスの info string) を推定する。
+// `.gather` の `[languages]` で `パターン = 言語` を追加・上書きできる。
+
+use globset::{Glob, GlobMatcher};
+use std::path::Path;
+
+/// 言語の判定表 (組み込み + `[languages]`)
+pub struct Languages {
+    /// (パターン, 言語)。後に書いたものが優先
+    overrides: Vec<(GlobMatcher, String)>,
+}
+
+impl Languages {
+    /// 不正なパターンは警告して無視する
+    pub fn new(overrides: &[(String, String)]) -> Self {
+        let overrides = overrides
+            .iter()
+            .filter_map(|(pat, lang)| match Glob::new(pat) {
+                Ok(g) => Some((g.compile_matcher(), lang.clone())),
+                Err(e) => {
+                    eprintln!("[languages] のパターンを無視します: {pat} ({e})");
+                    None
+                }
+            })
+            .collect();
+        Self { overrides }
+    }
+
+    /// `rel` (対象ディレクトリからの相対パス) の言語。分からなければ `None`
+    ///
+    /// `[languages]` のパターンは相対パスとファイル名の両方に対して照合する。
+    /// 値が空なら言語なしとして扱う。
+    pub fn detect(&self, rel: &Path) -> Option<String> {
+        self.lookup(rel)
+            .unwrap_or_else(|| builtin(rel).map(str::to_string))
+    }
+
+    /// `[languages]` に一致すれば `Some(言語)` (空値なら `Some(None)`)、一致しなければ `None`
+    pub fn lookup(&self, rel: &Path) -> Option<Option<String>> {
+        let name = rel.file_name().map(Path::new);
+        self.overrides
+            .iter()
+            .rev()
+            .find(|(m, _)| m.is_match(rel) || name.is_some_and(|n| m.is_match(n)))
+            .map(|(_, lang)| (!lang.is_empty()).then(|| lang.clone()))
+    }
+}
+
+/// 組み込みの対応表
+fn builtin(path: &Path) -> Option<&'static str> {
+    let name = path.file_name()?.to_str()?;
+    let by_name = match name {
+        "Dockerfile" | "Containerfile" => Some("dockerfile"),
+        "Makefile" | "makefile" | "GNUmakefile" => Some("makefile"),
+        "CMakeLists.txt" => Some("cmake"),
+        "Cargo.lock" => Some("toml"),
+        "Gemfile" | "Rakefile" => Some("ruby"),
+        ".gather" => Some("ini"),
+        ".gitignore" | ".dockerignore" => Some("gitignore"),
+        ".bashrc" | ".zshrc" | ".profile" => Some("bash"),
+        _ => None,
+    };
+    if by_name.is_some() {
+        return by_name;
+    }
+    let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+    let lang = match ext.as_str() {
+        "rs" => "rust",
+        "py" | "pyi" => "python",
+        "js" | "mjs" | "cjs" => "javascript",
+        "jsx" => "jsx",
+        "ts" | "mts" | "cts" => "typescript",
+        "tsx" => "tsx",
+        "go" => "go",
+        "java" => "java",
+        "kt" | "kts" => "kotlin",
+        "c" | "h" => "c",
+        "cc" | "cpp" | "cxx" | "hpp" | "hh" => "cpp",
+        "cs" => "csharp",
+        "rb" => "ruby",
+        "php" => "php",
+        "swift" => "swift",
+        "sh" | "bash" | "zsh" => "bash",
+        "ps1" => "powershell",
+        "sql" => "sql",
+        "html" | "htm" => "html",
+        "css" => "css",
+        "scss" => "scss",
+        "vue" => "vue",
+        "md" | "markdown" => "markdown",
+        "json" => "json",
+        "toml" => "toml",
+        "yaml" | "yml" => "yaml",
+        "xml" => "xml",
+        "ini" | "cfg" => "ini",
+        "dot" => "dot",
+        "txt" => "text",
+        _ => return None,
+    };
+    Some(lang)
+}
+
+/* --------------------------------------------------------------------- */
+/* tests                                                                 */
+/* --------------------------------------------------------------------- */
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect(langs: &Languages, rel: &str) -> Option<String> {
+        langs.detect(Path::new(rel))
+    }
+
+    #[test]
+    fn detects_by_extension_and_name() {
+        let langs = Languages::new(&[]);
+        assert_eq!(detect(&langs, "src/lib.rs").as_deref(), Some("rust"));
+        assert_eq!(detect(&langs, "a/B.TSX").as_deref(), Some("tsx"));
+        assert_eq!(
+            detect(&langs, "docker/Dockerfile").as_deref(),
+            Some("dockerfile")
+        );
+        assert_eq!(detect(&langs, "Cargo.toml").as_deref(), Some("toml"));
+        assert_eq!(detect(&langs, ".gather").as_deref(), Some("ini"));
+        assert_eq!(detect(&langs, "data.bin"), None);
+    }
+
+    #[test]
+    fn overrides_add_and_replace_mappings() {
+        let langs = Languages::new(&[
+            ("*.tpl".into(), "html".into()),
+            ("Justfile".into(), "make".into()),
+            ("docs/*.txt".into(), "".into()),
+            ("*.rs".into(), "rs".into()),
+        ]);
+        assert_eq!(detect(&langs, "views/a.tpl").as_deref(), Some("html"));
+        assert_eq!(detect(&langs, "tools/Justfile").as_deref(), Some("make"));
+        assert_eq!(detect(&langs, "docs/a.txt"), None, "empty value disables");
+        assert_eq!(detect(&langs, "notes.txt").as_deref(), Some("text"));
+        assert_eq!(detect(&langs, "src/lib.rs").as_deref(), Some("rs"));
+    }
+}
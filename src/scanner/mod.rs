@@ -1,18 +1,31 @@
 // src/scanner/mod.rs – v0.4.1  (outline セクション対応)
 
+mod budget;
 mod counter;
 pub mod detector;
-mod sort;
-mod utils;
-mod walker;
+mod format;
+mod lang;
+pub(crate) mod scope;
+pub(crate) mod sort;
+mod split;
+mod truncate;
+pub(crate) mod utils;
+pub(crate) mod walker;
 
+use budget::Block;
 use counter::ProcessCounter;
+use format::{FileDoc, FileMeta, Header, Renderer};
+use scope::Scopes;
 use sort::compare_dir_entry;
 use utils::{build_globset, is_binary_file};
 use walker::collect_entries;
 
-use crate::model::ConfigParams;
+use crate::gitignore::GitIgnore;
+
+use crate::model::{ConfigParams, SplitHeader, SplitLimit, Truncate};
 use crate::outline::registry::providers; // ←★ 共有プロバイダ
+use crate::outline::{write_md_symbols, RenderOptions};
+use crate::tokens::TokenCounter;
 
 use std::collections::HashMap;
 use std::fs::{self, File};
@@ -26,6 +39,10 @@ enum OmitReason {
     TooLarge,
     Pattern, // skip
     Outline, // ←★ new
+    /// `max_tokens` に収めるため本文をアウトラインに縮退
+    BudgetOutline,
+    /// `max_tokens` に収めるためツリーのみに縮退
+    Budget,
 }
 
 impl std::fmt::Display for OmitReason {
@@ -35,6 +52,8 @@ impl std::fmt::Display for OmitReason {
             OmitReason::TooLarge => write!(f, "too-large"),
             OmitReason::Pattern => write!(f, "pattern"),
             OmitReason::Outline => write!(f, "outline"),
+            OmitReason::BudgetOutline => write!(f, "budget-outline"),
+            OmitReason::Budget => write!(f, "budget"),
         }
     }
 }
@@ -45,23 +64,17 @@ pub fn run(
     output_file: &Path,
     config: &ConfigParams,
     _cli_include_patterns: &[String],
-) -> Result<(), String> {
+) -> Result<Vec<PathBuf>, String> {
     let mut counter = ProcessCounter::new();
 
-    /* ---------- globset 構築 ---------- */
-    let exclude_globset = build_globset(&config.exclude_patterns);
-    let skip_globset = build_globset(&config.skip_content_patterns);
-    let include_globset = if config.include_patterns.is_empty() {
-        None
-    } else {
-        build_globset(&config.include_patterns)
-    };
-    let outline_globset = build_globset(&config.outline_patterns); // ★
+    /* ---------- パターン (サブディレクトリの .gather も重ねる) ---------- */
+    let scopes = Scopes::new(target_dir, config);
+    let gitignore = config.use_gitignore.then(|| GitIgnore::new(target_dir));
 
     /* ============================================================
        1st pass – 省略判定マップ
     ============================================================ */
-    let mut file_entries = collect_entries(target_dir, &exclude_globset, true);
+    let mut file_entries = collect_entries(target_dir, &scopes, gitignore.as_ref(), true);
     file_entries.sort_by(|a, b| compare_dir_entry(a, b, target_dir));
     counter.set_total_files(file_entries.len());
 
@@ -70,29 +83,24 @@ pub fn run(
     for entry in &file_entries {
         let path = entry.path();
         let rel: PathBuf = path.strip_prefix(target_dir).unwrap_or(path).to_path_buf();
+        let config = &scopes.scope_for(&rel).config;
 
         /* include フィルタ */
-        if let Some(gs) = &include_globset {
-            if !gs.is_match(&rel) {
-                omitted.insert(rel, OmitReason::Pattern);
-                continue;
-            }
+        if !scopes.is_included(&rel) {
+            omitted.insert(rel, OmitReason::Pattern);
+            continue;
         }
 
         /* skip pattern */
-        if let Some(gs) = &skip_globset {
-            if gs.is_match(&rel) {
-                omitted.insert(rel, OmitReason::Pattern);
-                continue;
-            }
+        if scopes.is_skipped(&rel) {
+            omitted.insert(rel, OmitReason::Pattern);
+            continue;
         }
 
         /* outline pattern – skip より後ろ / exclude より前 */
-        if let Some(gs) = &outline_globset {
-            if gs.is_match(&rel) {
-                omitted.insert(rel, OmitReason::Outline);
-                continue;
-            }
+        if scopes.is_outlined(&rel) {
+            omitted.insert(rel, OmitReason::Outline);
+            continue;
         }
 
         /* バイナリ */
@@ -112,22 +120,402 @@ pub fn run(
     }
 
     /* ============================================================
-       2nd pass – ツリー出力
+       2nd pass – 本文 / アウトラインを各ファイルのブロックに描画
     ============================================================ */
-    let mut tree_entries = walker::collect_entries(target_dir, &exclude_globset, false);
+    let tokens = TokenCounter::from_config(config, target_dir);
+    counter.set_top_n(config.token_top_n);
+    let fmt = format::renderer(config.output_format);
+    // 言語と、見出し・囲みまで含めた 1 ファイル分のトークン数を付ける
+    let measured = |mut block: Block| {
+        block.meta.language = scopes.language(&block.rel);
+        let rel = block.rel.to_string_lossy().to_string();
+        let mut out = Vec::new();
+        fmt.file(&mut out, &block_doc(1, &rel, &block, None));
+        block.tokens = tokens.count(&String::from_utf8_lossy(&out));
+        block
+    };
+
+    let mut blocks: Vec<Block> = Vec::new();
+    for (idx, entry) in file_entries.iter().enumerate() {
+        let path = entry.path();
+        let rel: PathBuf = path.strip_prefix(target_dir).unwrap_or(path).to_path_buf();
+        let rel_str = rel.to_string_lossy().to_string();
+
+        /* --- 省略判定 ----------------------------------------- */
+        let ((body, meta), outlined) = match omitted.get(&rel) {
+            Some(OmitReason::Pattern) => {
+                counter.increment_skipped_pattern();
+                continue;
+            }
+            Some(OmitReason::Binary) => {
+                counter.increment_skipped_binary();
+                continue;
+            }
+            Some(OmitReason::TooLarge) => {
+                counter.increment_skipped_size();
+                continue;
+            }
+            // 予算による縮退は描画後に決まる
+            Some(OmitReason::BudgetOutline | OmitReason::Budget) => continue,
+            Some(OmitReason::Outline) => {
+                // アウトラインのみを出力
+                eprintln!(
+                    "({}/{}) Outline: {}",
+                    idx + 1,
+                    file_entries.len(),
+                    path.display()
+                );
+                let scope = scopes.scope_for(&rel);
+                (render_outline(path, &rel_str, &scope.config), true)
+            }
+            None => {
+                eprintln!(
+                    "({}/{}) Processing: {}",
+                    idx + 1,
+                    file_entries.len(),
+                    path.display()
+                );
+                let scope = scopes.scope_for(&rel);
+                (
+                    render_body(path, scopes.truncation(&rel), &scope.config),
+                    false,
+                )
+            }
+        };
+        blocks.push(measured(Block {
+            rel,
+            path: path.to_path_buf(),
+            body,
+            tokens: 0,
+            outlined,
+            meta,
+        }));
+    }
+
+    /* ============================================================
+       3rd pass – ツリー出力 (必要ならトークン数を注記)
+    ============================================================ */
+    let mut tree_entries = walker::collect_entries(target_dir, &scopes, gitignore.as_ref(), false);
     tree_entries.sort_by(|a, b| compare_dir_entry(a, b, target_dir));
 
-    let mut outfile = File::create(output_file).map_err(|e| {
-        format!(
-            "出力ファイルを作成できません: {} - {}",
-            output_file.display(),
-            e
-        )
-    })?;
+    /* --- トークン予算: full → outline → tree-only ------------------ */
+    if let Some(limit) = config.max_tokens {
+        // ツリーは最悪の形で見積もる: 全ブロックに予算の注記が付き、`[tokens:N]` は縮退前の値
+        let mut worst = omitted.clone();
+        let mut worst_tokens = HashMap::new();
+        for b in &blocks {
+            worst
+                .entry(b.rel.clone())
+                .or_insert(OmitReason::BudgetOutline);
+            worst_tokens.insert(b.rel.clone(), b.tokens);
+        }
+        let tree = frame(
+            fmt.as_ref(),
+            &render_tree(
+                target_dir,
+                &tree_entries,
+                &worst,
+                config.tree_tokens.then_some(&worst_tokens),
+            ),
+        );
+        let priority: Vec<_> = config
+            .priority_patterns
+            .iter()
+            .filter_map(|p| build_globset(std::slice::from_ref(p)))
+            .collect();
+        let degraded = budget::fit(
+            &mut blocks,
+            &mut omitted,
+            limit,
+            tokens.count(&String::from_utf8_lossy(&tree)),
+            &priority,
+            |b| {
+                providers()
+                    .iter()
+                    .any(|p| p.supports_dyn(&b.path))
+                    .then(|| {
+                        let scope = scopes.scope_for(&b.rel);
+                        let (body, meta) =
+                            render_outline(&b.path, &b.rel.to_string_lossy(), &scope.config);
+                        measured(Block {
+                            rel: b.rel.clone(),
+                            path: b.path.clone(),
+                            body,
+                            tokens: 0,
+                            outlined: true,
+                            meta,
+                        })
+                    })
+            },
+        );
+        counter.set_budget(limit, degraded.outlined, degraded.dropped);
+    }
+
+    let mut file_tokens: HashMap<PathBuf, usize> = HashMap::new();
+    for b in &blocks {
+        counter.increment_processed();
+        counter.record_file_tokens(b.rel.to_string_lossy().to_string(), b.tokens);
+        file_tokens.insert(b.rel.clone(), b.tokens);
+    }
+
+    let tree = render_tree(
+        target_dir,
+        &tree_entries,
+        &omitted,
+        config.tree_tokens.then_some(&file_tokens),
+    );
+    let frame_tokens = tokens.count(&String::from_utf8_lossy(&frame(fmt.as_ref(), &tree)));
+    let total = frame_tokens + file_tokens.values().sum::<usize>();
+    counter.set_tokens(total, tokens.method());
+
+    /* --- 出力するファイル: ブロック → ツリーのみのファイル ------------ */
+    let rels: Vec<String> = blocks
+        .iter()
+        .map(|b| b.rel.to_string_lossy().to_string())
+        .collect();
+    let mut docs: Vec<FileDoc> = blocks
+        .iter()
+        .zip(&rels)
+        .enumerate()
+        .map(|(i, (b, rel))| {
+            let reason = omitted.get(&b.rel).map(|r| r.to_string());
+            block_doc(i + 1, rel, b, reason)
+        })
+        .collect();
+    let tree_only: Vec<(String, FileMeta, String)> = file_entries
+        .iter()
+        .filter_map(|entry| {
+            let path = entry.path();
+            let rel = path.strip_prefix(target_dir).unwrap_or(path);
+            match omitted.get(rel)? {
+                OmitReason::Outline | OmitReason::BudgetOutline => None,
+                reason => Some((
+                    rel.to_string_lossy().to_string(),
+                    FileMeta {
+                        language: scopes.language(rel),
+                        ..file_meta(path)
+                    },
+                    reason.to_string(),
+                )),
+            }
+        })
+        .collect();
+    for (rel, meta, reason) in &tree_only {
+        docs.push(FileDoc {
+            index: docs.len() + 1,
+            rel,
+            content: None,
+            meta,
+            omitted: Some(reason.clone()),
+        });
+    }
+
+    /* ============================================================
+       書き出し – ツリー → 各ファイル (`split` があればパートごと)
+    ============================================================ */
+    let block_tokens: Vec<usize> = blocks.iter().map(|b| b.tokens).collect();
+    let summary = counter.to_json();
+    let written = write_output(
+        output_file,
+        fmt.as_ref(),
+        &Header {
+            tree: Some(&tree),
+            summary: Some(&summary),
+            ..Header::default()
+        },
+        &docs,
+        &block_tokens,
+        config,
+        &tokens,
+    )?;
+
+    /* ============================================================
+       summary
+    ============================================================ */
+    counter.print_summary();
+    if written.len() > 1 {
+        eprintln!("- Parts: {}", written.len());
+        for p in &written {
+            eprintln!("    {}", p.display());
+        }
+    }
+    Ok(written)
+}
+
+/// ブロックを `Renderer` に渡す形にする
+fn block_doc<'a>(
+    index: usize,
+    rel: &'a str,
+    block: &'a Block,
+    omitted: Option<String>,
+) -> FileDoc<'a> {
+    FileDoc {
+        index,
+        rel,
+        content: Some(&block.body),
+        meta: &block.meta,
+        omitted,
+    }
+}
+
+/// 出力ファイルを書き出し、書き出したパスを返す。
+///
+/// `split` の上限を超える場合は `output_partN.ext` へ分割し、各パートの先頭に
+/// パート見出しとツリー (または そのパートのファイル一覧) を置く。
+/// `block_tokens` は `docs` 先頭のブロック分の (計算済み) トークン数。
+fn write_output(
+    output_file: &Path,
+    fmt: &dyn Renderer,
+    header: &Header<'_>,
+    docs: &[FileDoc<'_>],
+    block_tokens: &[usize],
+    config: &ConfigParams,
+    tokens: &TokenCounter,
+) -> Result<Vec<PathBuf>, String> {
+    let create = |path: &Path| {
+        File::create(path)
+            .map_err(|e| format!("出力ファイルを作成できません: {} - {}", path.display(), e))
+    };
+    let render = |f: &dyn Fn(&mut Vec<u8>)| {
+        let mut out = Vec::new();
+        f(&mut out);
+        out
+    };
+    let rendered: Vec<Vec<u8>> = docs
+        .iter()
+        .map(|doc| render(&|out| fmt.file(out, doc)))
+        .collect();
+    // パートごとのヘッダ (ツリー または ファイル一覧)
+    let part_header = |part: Option<(usize, usize)>, range: std::ops::Range<usize>| {
+        let files: Vec<&str> = docs[range]
+            .iter()
+            .filter(|d| d.content.is_some())
+            .map(|d| d.rel)
+            .collect();
+        let by_index = part.is_some() && config.split_header == SplitHeader::Index;
+        render(&|out| {
+            fmt.header(
+                out,
+                &Header {
+                    part,
+                    tree: if by_index { None } else { header.tree },
+                    files: by_index.then_some(files.as_slice()),
+                    summary: header.summary,
+                },
+            )
+        })
+    };
+
+    let parts = match config.split {
+        Some(limit) => {
+            let size = |text: &[u8]| {
+                split::measure(limit, text, || tokens.count(&String::from_utf8_lossy(text)))
+            };
+            // パート見出しの桁は十分大きく見積もる。ファイル一覧の分は各ファイルに含める
+            let empty = part_header(Some((99, 99)), 0..0);
+            let header_cost = size(&empty)
+                + size(&render(&|out| {
+                    fmt.begin(out);
+                    fmt.end(out)
+                }));
+            let costs: Vec<usize> = rendered
+                .iter()
+                .enumerate()
+                .map(|(i, body)| {
+                    let body = match (limit, block_tokens.get(i)) {
+                        (SplitLimit::Tokens(_), Some(&n)) => n,
+                        _ => size(body),
+                    };
+                    let listed = match config.split_header {
+                        SplitHeader::Tree => 0,
+                        SplitHeader::Index => size(&part_header(Some((99, 99)), i..i + 1))
+                            .saturating_sub(size(&empty)),
+                    };
+                    body + listed + size(fmt.separator())
+                })
+                .collect();
+            split::pack(&costs, header_cost, split::max_of(limit))
+        }
+        None => std::iter::once(0..docs.len()).collect(),
+    };
+
+    let write_files = |out: &mut File, range: std::ops::Range<usize>| {
+        out.write_all(&render(&|o| fmt.begin(o))).ok();
+        for (n, body) in rendered[range].iter().enumerate() {
+            if n > 0 {
+                out.write_all(fmt.separator()).ok();
+            }
+            out.write_all(body).ok();
+        }
+        out.write_all(&render(&|o| fmt.end(o))).ok();
+    };
 
-    writeln!(outfile, "```").ok();
-    for entry in &tree_entries {
+    if parts.len() == 1 {
+        let mut outfile = create(output_file)?;
+        outfile.write_all(&part_header(None, 0..0)).ok();
+        write_files(&mut outfile, 0..docs.len());
+        return Ok(vec![output_file.to_path_buf()]);
+    }
+
+    let mut written = Vec::new();
+    for (k, range) in parts.iter().enumerate() {
+        let path = split::part_path(output_file, k + 1);
+        let mut outfile = create(&path)?;
+        outfile
+            .write_all(&part_header(Some((k + 1, parts.len())), range.clone()))
+            .ok();
+        write_files(&mut outfile, range.clone());
+        written.push(path);
+    }
+    Ok(written)
+}
+
+/// ツリー + ファイル群の囲み (ブロック以外の固定部分)
+fn frame(fmt: &dyn Renderer, tree: &str) -> Vec<u8> {
+    let mut out = Vec::new();
+    fmt.header(
+        &mut out,
+        &Header {
+            tree: Some(tree),
+            ..Header::default()
+        },
+    );
+    fmt.begin(&mut out);
+    fmt.end(&mut out);
+    out
+}
+
+/* ---------------------------------------------------------------
+   描画ヘルパ
+--------------------------------------------------------------- */
+
+/// ディレクトリツリー (1 行 1 エントリ。囲みは `Renderer::tree`)
+///
+/// `tokens` を渡すと、ファイルには自身の、ディレクトリには配下合計のトークン数を
+/// `[tokens:N]` として付ける。
+fn render_tree(
+    target_dir: &Path,
+    entries: &[walkdir::DirEntry],
+    omitted: &HashMap<PathBuf, OmitReason>,
+    tokens: Option<&HashMap<PathBuf, usize>>,
+) -> String {
+    use std::fmt::Write as _;
+
+    // 各ファイルのトークン数を祖先すべてに足し込む (ファイル自身も含む)
+    let totals: Option<HashMap<&Path, usize>> = tokens.map(|t| {
+        let mut totals = HashMap::new();
+        for (file, n) in t {
+            for dir in file.ancestors() {
+                *totals.entry(dir).or_insert(0) += n;
+            }
+        }
+        totals
+    });
+
+    let mut out = String::new();
+    for entry in entries {
         let path = entry.path();
+        let is_dir = entry.file_type().is_dir();
         let rel: PathBuf = path.strip_prefix(target_dir).unwrap_or(path).to_path_buf();
         let rel_str = rel.to_string_lossy();
         let indent = "    ".repeat(rel.components().count().saturating_sub(1));
@@ -136,109 +524,116 @@ pub fn run(
             .map(|s| s.to_string_lossy())
             .unwrap_or_else(|| rel_str.clone());
 
+        let mut notes = Vec::new();
         if let Some(reason) = omitted.get(&rel) {
-            writeln!(outfile, "{indent}{name}   [omitted:{reason}]").ok();
-        } else if path.is_dir() {
-            writeln!(outfile, "{indent}{name}/").ok();
+            notes.push(format!("[omitted:{reason}]"));
+        }
+        if let Some(totals) = &totals {
+            let n = totals.get(rel.as_path()).copied().unwrap_or(0);
+            if n > 0 {
+                notes.push(format!("[tokens:{n}]"));
+            }
+        }
+
+        let slash = if is_dir { "/" } else { "" };
+        if notes.is_empty() {
+            writeln!(out, "{indent}{name}{slash}").ok();
         } else {
-            writeln!(outfile, "{indent}{name}").ok();
+            writeln!(out, "{indent}{name}{slash}   {}", notes.join(" ")).ok();
         }
     }
-    writeln!(outfile, "```").ok();
-    writeln!(outfile).ok();
-
-    /* ============================================================
-       3rd pass – 本文 / アウトライン出力
-    ============================================================ */
-    for (idx, entry) in file_entries.iter().enumerate() {
-        let path = entry.path();
-        let rel: PathBuf = path.strip_prefix(target_dir).unwrap_or(path).to_path_buf();
-        let rel_str = rel.to_string_lossy();
+    out
+}
 
-        /* --- 省略判定 ----------------------------------------- */
-        if let Some(reason) = omitted.get(&rel) {
-            match reason {
-                OmitReason::Pattern => counter.increment_skipped_pattern(),
-                OmitReason::Binary => counter.increment_skipped_binary(),
-                OmitReason::TooLarge => counter.increment_skipped_size(),
-                OmitReason::Outline => {
-                    // アウトラインのみを出力
-                    eprintln!(
-                        "({}/{}) Outline: {}",
-                        idx + 1,
-                        file_entries.len(),
-                        path.display()
-                    );
-                    writeln!(outfile, "### {}", rel_str).ok();
-                    writeln!(outfile, "```").ok();
-
-                    let src = fs::read_to_string(path).unwrap_or_default();
-                    if let Some(p) = providers().iter().find(|p| p.supports_dyn(path)) {
-                        if let Ok(syms) = p.extract_dyn(path, &src) {
-                            for s in syms {
-                                writeln!(outfile, "- **{}** {}", s.kind, s.ident).ok();
-                            }
-                        }
-                    } else {
-                        writeln!(outfile, "(outline not supported)").ok();
-                    }
-
-                    writeln!(outfile, "```").ok();
-                    writeln!(outfile).ok();
-                    counter.increment_processed();
-                }
-            }
-            continue; // 本文は出力しない
-        }
+/// サイズだけのメタ情報 (言語は呼び出し側で `Languages` から付ける)
+fn file_meta(path: &Path) -> FileMeta {
+    FileMeta {
+        size: fs::metadata(path).ok().map(|m| m.len()),
+        ..FileMeta::default()
+    }
+}
 
-        /* --- 本文出力 ----------------------------------------- */
-        eprintln!(
-            "({}/{}) Processing: {}",
-            idx + 1,
-            file_entries.len(),
-            path.display()
-        );
+/// `[outline]` 対象ファイルの中身 (見出し・囲みは `Renderer::file`)
+fn render_outline(path: &Path, rel_str: &str, config: &ConfigParams) -> (Vec<u8>, FileMeta) {
+    let mut out = Vec::new();
+    let mut meta = FileMeta {
+        outline: true,
+        ..file_meta(path)
+    };
 
-        writeln!(outfile, "### {}", rel_str).ok();
-        writeln!(outfile, "```").ok();
+    let src = fs::read_to_string(path).unwrap_or_default();
+    meta.lines = Some(src.lines().count());
+    if let Some(p) = providers().iter().find(|p| p.supports_dyn(path)) {
+        if let Ok(syms) = p.extract_dyn(path, &src) {
+            let render = RenderOptions::from(config);
+            write_md_symbols(&mut out, rel_str, syms.clone(), render).ok();
+            meta.symbols = Some(render.shape(syms));
+        }
+    } else {
+        writeln!(out, "(outline not supported)").ok();
+    }
+    (out, meta)
+}
 
-        let file = match File::open(path) {
-            Ok(f) => f,
+/// 本文 (`max_lines` を超えたら方式に従って打ち切り。行数は最後まで数える)
+///
+/// 省略した箇所には `... (省略: N 行) ...` を置く。
+/// `line_numbers` なら各行に元ファイルでの行番号を右寄せで付ける。
+fn render_body(
+    path: &Path,
+    (strategy, max_lines): (Truncate, usize),
+    config: &ConfigParams,
+) -> (Vec<u8>, FileMeta) {
+    let mut out = Vec::new();
+    let mut meta = file_meta(path);
+    let file = match File::open(path) {
+        Ok(f) => f,
+        Err(e) => {
+            writeln!(out, "Error: {}", e).ok();
+            return (out, meta);
+        }
+    };
+    let mut lines = Vec::new();
+    let mut error = None;
+    for line in BufReader::new(file).lines() {
+        match line {
+            Ok(l) => lines.push(l),
             Err(e) => {
-                writeln!(outfile, "Error: {}", e).ok();
-                writeln!(outfile, "```").ok();
-                writeln!(outfile).ok();
-                counter.increment_processed();
-                continue;
-            }
-        };
-        let reader = BufReader::new(file);
-        let mut lines = 0;
-        for line in reader.lines() {
-            match line {
-                Ok(l) => {
-                    if lines >= config.max_lines {
-                        writeln!(outfile, "...").ok();
-                        writeln!(outfile, "(省略)").ok();
-                        break;
-                    }
-                    writeln!(outfile, "{l}").ok();
-                    lines += 1;
-                }
-                Err(e) => {
-                    writeln!(outfile, "Error reading line: {}", e).ok();
-                    break;
-                }
+                error = Some(e);
+                break;
             }
         }
-        writeln!(outfile, "```").ok();
-        writeln!(outfile).ok();
-        counter.increment_processed();
     }
+    meta.lines = Some(lines.len());
+    meta.truncated = lines.len() > max_lines;
 
-    /* ============================================================
-       summary
-    ============================================================ */
-    counter.print_summary();
-    Ok(())
+    let width = lines.len().to_string().len();
+    let elided = |out: &mut Vec<u8>, n: usize| {
+        writeln!(out, "... (省略: {n} 行) ...").ok();
+    };
+    let mut next = 0;
+    for range in truncate::keep(lines.len(), max_lines, strategy) {
+        if range.is_empty() {
+            continue;
+        }
+        if range.start > next {
+            elided(&mut out, range.start - next);
+        }
+        for no in range.clone() {
+            let l = &lines[no];
+            if config.line_numbers {
+                writeln!(out, "{:>width$} | {l}", no + 1).ok();
+            } else {
+                writeln!(out, "{l}").ok();
+            }
+        }
+        next = range.end;
+    }
+    if next < lines.len() {
+        elided(&mut out, lines.len() - next);
+    }
+    if let Some(e) = error {
+        writeln!(out, "Error reading line: {}", e).ok();
+    }
+    (out, meta)
 }
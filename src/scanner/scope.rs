@@ -0,0 +1,260 @@
+// src/scanner/scope.rs
+//
+// サブディレクトリの `.gather` を重ねた「ディレクトリごとの設定」
+//
+//   root/.gather          … 対象ディレクトリ全体 (CLI 反映済みの ConfigParams)
+//   root/pkg/.gather      … pkg/ 配下だけに効く。パターンは pkg/ からの相対パス
+//   root/pkg/sub/.gather  … さらに深い階層ほど優先
+//
+// .gitignore と同じく、走査中に必要になったディレクトリから読み込む。
+// パターンは浅い階層から順に評価し、最後に一致したもの (= 深い階層) が勝つ。
+// 設定値は親の実効設定を引き継ぎ、`config::load_scoped_config` が許すキーだけ上書きする。
+
+use super::lang::Languages;
+use super::truncate::Truncation;
+use super::utils::{build_globset, build_patterns, PatternSet};
+use crate::config::load_scoped_config;
+use crate::model::{ConfigParams, Truncate};
+use globset::GlobSet;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+/// 1 つの `.gather` が効く範囲
+pub struct Scope {
+    /// 対象ディレクトリからの相対パス (ルートは空)
+    dir: PathBuf,
+    /// このディレクトリ配下の実効設定
+    pub config: ConfigParams,
+    exclude: Option<PatternSet>,
+    skip: Option<PatternSet>,
+    outline: Option<PatternSet>,
+    include: Option<GlobSet>,
+    languages: Languages,
+    truncation: Truncation,
+}
+
+impl Scope {
+    fn new(dir: PathBuf, config: ConfigParams) -> Self {
+        Self {
+            exclude: build_patterns(&config.exclude_patterns),
+            skip: build_patterns(&config.skip_content_patterns),
+            outline: build_patterns(&config.outline_patterns),
+            include: build_globset(&config.include_patterns),
+            languages: Languages::new(&config.language_overrides),
+            truncation: Truncation::new(&config),
+            dir,
+            config,
+        }
+    }
+
+    /// `rel` (対象ディレクトリからの相対パス) をこのスコープ基準に直す
+    fn local<'p>(&self, rel: &'p Path) -> &'p Path {
+        rel.strip_prefix(&self.dir).unwrap_or(rel)
+    }
+}
+
+/// 対象ディレクトリ配下の `.gather` 一式
+pub struct Scopes {
+    target_dir: PathBuf,
+    root: Rc<Scope>,
+    /// 相対ディレクトリ → そこに効くスコープ (浅い順、そのディレクトリの `.gather` を含む)
+    chains: RefCell<HashMap<PathBuf, Rc<[Rc<Scope>]>>>,
+}
+
+impl Scopes {
+    pub fn new(target_dir: &Path, config: &ConfigParams) -> Self {
+        Self {
+            target_dir: target_dir.to_path_buf(),
+            root: Rc::new(Scope::new(PathBuf::new(), config.clone())),
+            chains: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// `rel` に効くスコープ (浅い順)。`rel` 自身のディレクトリは含めない
+    fn chain(&self, rel: &Path) -> Rc<[Rc<Scope>]> {
+        self.dir_chain(rel.parent().unwrap_or(Path::new("")))
+    }
+
+    /// ディレクトリ `dir` 配下に効くスコープ (浅い順)。ディレクトリごとにキャッシュする
+    fn dir_chain(&self, dir: &Path) -> Rc<[Rc<Scope>]> {
+        if let Some(chain) = self.chains.borrow().get(dir) {
+            return chain.clone();
+        }
+        let chain: Rc<[Rc<Scope>]> = match dir.parent() {
+            None => Rc::new([self.root.clone()]),
+            Some(parent) => {
+                let parent = self.dir_chain(parent);
+                let file = self.target_dir.join(dir).join(".gather");
+                if file.is_file() {
+                    let config = load_scoped_config(&file, &parent[parent.len() - 1].config);
+                    let mut chain = parent.to_vec();
+                    chain.push(Rc::new(Scope::new(dir.to_path_buf(), config)));
+                    chain.into()
+                } else {
+                    parent
+                }
+            }
+        };
+        self.chains
+            .borrow_mut()
+            .insert(dir.to_path_buf(), chain.clone());
+        chain
+    }
+
+    /// 浅い階層から順にパターンを評価し、最後に一致したものを採る
+    fn decide(&self, rel: &Path, set: impl Fn(&Scope) -> Option<&PatternSet>) -> bool {
+        self.chain(rel)
+            .iter()
+            .filter_map(|s| set(s).and_then(|ps| ps.matched(s.local(rel))))
+            .last()
+            .unwrap_or(false)
+    }
+
+    pub fn is_excluded(&self, rel: &Path) -> bool {
+        self.decide(rel, |s| s.exclude.as_ref())
+    }
+
+    pub fn is_skipped(&self, rel: &Path) -> bool {
+        self.decide(rel, |s| s.skip.as_ref())
+    }
+
+    pub fn is_outlined(&self, rel: &Path) -> bool {
+        self.decide(rel, |s| s.outline.as_ref())
+    }
+
+    /// 最も深い `[include]` を持つスコープに一致するか (どこにも無ければ真)
+    pub fn is_included(&self, rel: &Path) -> bool {
+        self.chain(rel)
+            .iter()
+            .rev()
+            .find_map(|s| s.include.as_ref().map(|gs| gs.is_match(s.local(rel))))
+            .unwrap_or(true)
+    }
+
+    /// 除外ディレクトリ `dir` の配下が `!pattern` で再包含されうるか
+    pub fn may_reinclude_under(&self, dir: &Path) -> bool {
+        // `dir` 自身の `.gather` も含める
+        self.dir_chain(dir).iter().any(|s| {
+            s.exclude
+                .as_ref()
+                .is_some_and(|ps| ps.may_reinclude_under(s.local(dir)))
+        })
+    }
+
+    /// `rel` に効く最も深いスコープ (設定値はここから読む)
+    pub fn scope_for(&self, rel: &Path) -> Rc<Scope> {
+        let chain = self.chain(rel);
+        chain[chain.len() - 1].clone()
+    }
+
+    /// コードフェンスの言語 (深い階層の `[languages]` → 組み込みの対応表)
+    pub fn language(&self, rel: &Path) -> Option<String> {
+        let chain = self.chain(rel);
+        chain[1..]
+            .iter()
+            .rev()
+            .find_map(|s| s.languages.lookup(s.local(rel)))
+            .unwrap_or_else(|| self.root.languages.detect(rel))
+    }
+
+    /// 打ち切り方 (深い階層の `[truncate]` → 最も深いスコープの既定値)
+    pub fn truncation(&self, rel: &Path) -> (Truncate, usize) {
+        let chain = self.chain(rel);
+        chain
+            .iter()
+            .rev()
+            .find_map(|s| s.truncation.rule_for(s.local(rel)))
+            .unwrap_or_else(|| {
+                let deepest = &chain[chain.len() - 1];
+                deepest.truncation.for_path(deepest.local(rel))
+            })
+    }
+}
+
+/* --------------------------------------------------------------------- */
+/* tests                                                                 */
+/* --------------------------------------------------------------------- */
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Source;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn nested_gather_is_scoped_and_overrides_root() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("pkg/sub")).unwrap();
+        fs::write(
+            root.join("pkg/.gather"),
+            "[settings]\nmax_lines = 20\noutput_format = xml\n\n\
+             [exclude]\nfixtures/\n!*.keep\n\n[skip]\n*.snap\n\n[truncate]\n*.log = tail\n",
+        )
+        .unwrap();
+        let cfg = ConfigParams {
+            exclude_patterns: vec!["*.keep".into()],
+            skip_content_patterns: vec!["*.lock".into()],
+            ..ConfigParams::default()
+        };
+        let scopes = Scopes::new(root, &cfg);
+
+        assert!(scopes.is_excluded(Path::new("a.keep")));
+        assert!(!scopes.is_excluded(Path::new("pkg/a.keep")), "deeper wins");
+        assert!(scopes.is_excluded(Path::new("pkg/fixtures/x.json")));
+        assert!(!scopes.is_excluded(Path::new("fixtures/x.json")), "scoped");
+        assert!(
+            scopes.is_skipped(Path::new("pkg/sub/a.lock")),
+            "root still applies"
+        );
+        assert!(scopes.is_skipped(Path::new("pkg/sub/a.snap")));
+        assert!(!scopes.is_skipped(Path::new("a.snap")));
+
+        let pkg = scopes.scope_for(Path::new("pkg/sub/lib.rs"));
+        assert_eq!(pkg.config.max_lines, 20);
+        assert_eq!(
+            pkg.config.output_format, cfg.output_format,
+            "output settings stay with the root"
+        );
+        assert_eq!(
+            scopes.truncation(Path::new("pkg/a.log")),
+            (Truncate::Tail, 20)
+        );
+        assert_eq!(
+            scopes.truncation(Path::new("a.log")),
+            (Truncate::Head, 1000)
+        );
+    }
+
+    #[test]
+    fn cli_values_beat_nested_settings() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("pkg")).unwrap();
+        fs::write(root.join("pkg/.gather"), "[settings]\nmax_lines = 20\n").unwrap();
+        let cfg = ConfigParams {
+            max_lines: 5,
+            sources: [("max_lines".to_string(), Source::Cli)].into(),
+            ..ConfigParams::default()
+        };
+        let scopes = Scopes::new(root, &cfg);
+        assert_eq!(scopes.scope_for(Path::new("pkg/a.rs")).config.max_lines, 5);
+    }
+    #[test]
+    fn chains_are_cached_per_directory() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("pkg/sub")).unwrap();
+        fs::write(root.join("pkg/.gather"), "[settings]\nmax_lines = 20\n").unwrap();
+        let scopes = Scopes::new(root, &ConfigParams::default());
+
+        let a = scopes.chain(Path::new("pkg/sub/a.rs"));
+        let b = scopes.chain(Path::new("pkg/sub/b.rs"));
+        assert!(Rc::ptr_eq(&a, &b));
+        assert_eq!(a.len(), 2);
+        assert!(Rc::ptr_eq(&a, &scopes.dir_chain(Path::new("pkg"))));
+        assert_eq!(scopes.chain(Path::new("a.rs")).len(), 1);
+    }
+}
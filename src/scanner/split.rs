@@ -0,0 +1,102 @@
+// src/scanner/split.rs
+//
+// 出力を `split` の上限ごとに `output_part1.txt`, `output_part2.txt`, … へ分割する。
+//
+// - 1 ファイルのブロック (フェンス) は決して途中で切らない。
+// - 各パートの先頭にはツリー (または そのパートのファイル一覧) を繰り返す。
+// - 1 ブロックだけで上限を超える場合は、そのブロック単独で 1 パートにする。
+
+use crate::model::SplitLimit;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+/// `limit` の単位で測ったテキストの大きさ
+pub fn measure(limit: SplitLimit, text: &[u8], tokens: impl FnOnce() -> usize) -> usize {
+    match limit {
+        SplitLimit::Bytes(_) => text.len(),
+        SplitLimit::Lines(_) => text.iter().filter(|&&b| b == b'\n').count(),
+        SplitLimit::Tokens(_) => tokens(),
+    }
+}
+
+/// 上限値
+pub fn max_of(limit: SplitLimit) -> usize {
+    match limit {
+        SplitLimit::Bytes(n) | SplitLimit::Tokens(n) | SplitLimit::Lines(n) => n,
+    }
+}
+
+/// 各ブロックのコスト `costs` を、`header` + 合計が `limit` に収まるよう先頭から詰める。
+pub fn pack(costs: &[usize], header: usize, limit: usize) -> Vec<Range<usize>> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut used = header;
+    for (i, &c) in costs.iter().enumerate() {
+        if i > start && used + c > limit {
+            parts.push(start..i);
+            start = i;
+            used = header;
+        }
+        used += c;
+    }
+    if start < costs.len() || parts.is_empty() {
+        parts.push(start..costs.len());
+    }
+    parts
+}
+
+/// `out/result.txt` → `out/result_part3.txt`
+pub fn part_path(output: &Path, n: usize) -> PathBuf {
+    let stem = output
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let name = match output.extension() {
+        Some(ext) => format!("{stem}_part{n}.{}", ext.to_string_lossy()),
+        None => format!("{stem}_part{n}"),
+    };
+    output.with_file_name(name)
+}
+
+/* --------------------------------------------------------------------- */
+/* tests                                                                 */
+/* --------------------------------------------------------------------- */
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packs_greedily_without_splitting_blocks() {
+        assert_eq!(pack(&[3, 3, 3, 3], 2, 8), vec![0..2, 2..4]);
+        // 単独で上限を超えるブロックは 1 パートを占有する
+        assert_eq!(pack(&[2, 20, 2], 1, 8), vec![0..1, 1..2, 2..3]);
+        assert_eq!(pack(&[], 1, 8), vec![0..0]);
+    }
+
+    #[test]
+    fn part_path_keeps_stem_and_extension() {
+        assert_eq!(
+            part_path(Path::new("out/result.txt"), 2),
+            PathBuf::from("out/result_part2.txt")
+        );
+        assert_eq!(part_path(Path::new("dump"), 1), PathBuf::from("dump_part1"));
+    }
+
+    #[test]
+    fn measures_in_limit_units() {
+        let text = b"a\nbb\n";
+        assert_eq!(measure(SplitLimit::Bytes(0), text, || 99), 5);
+        assert_eq!(measure(SplitLimit::Lines(0), text, || 99), 2);
+        assert_eq!(measure(SplitLimit::Tokens(0), text, || 99), 99);
+    }
+
+    #[test]
+    fn split_limit_parses_units_and_rejects_overflow() {
+        assert_eq!("500kb".parse(), Ok(SplitLimit::Bytes(500_000)));
+        assert_eq!("30_000tokens".parse(), Ok(SplitLimit::Tokens(30_000)));
+        assert_eq!("2000lines".parse(), Ok(SplitLimit::Lines(2000)));
+        let huge = format!("{}mb", usize::MAX / 1000);
+        assert!(huge.parse::<SplitLimit>().is_err());
+        assert!("99999999999999999999999kb".parse::<SplitLimit>().is_err());
+    }
+}
@@ -0,0 +1,115 @@
+// src/scanner/truncate.rs
+//
+// `max_lines` を超えた本文のどの行を残すか
+//
+//   head      : 先頭 max_lines 行 (既定)
+//   tail      : 末尾 max_lines 行
+//   head_tail : 先頭と末尾を半分ずつ
+//
+// 方式と行数は `[truncate]` のパターン (後に書いたものが優先) で上書きできる。
+
+use crate::model::{ConfigParams, Truncate};
+use globset::{Glob, GlobMatcher};
+use std::ops::Range;
+use std::path::Path;
+
+/// ファイルごとの打ち切り方
+pub struct Truncation {
+    default: (Truncate, usize),
+    rules: Vec<(GlobMatcher, Truncate, Option<usize>)>,
+}
+
+impl Truncation {
+    /// 不正なパターンは警告して無視する
+    pub fn new(config: &ConfigParams) -> Self {
+        let rules = config
+            .truncate_rules
+            .iter()
+            .filter_map(|r| match Glob::new(&r.pattern) {
+                Ok(g) => Some((g.compile_matcher(), r.strategy, r.max_lines)),
+                Err(e) => {
+                    eprintln!("[truncate] のパターンを無視します: {} ({e})", r.pattern);
+                    None
+                }
+            })
+            .collect();
+        Self {
+            default: (config.truncate, config.max_lines),
+            rules,
+        }
+    }
+
+    /// `rel` (対象ディレクトリからの相対パス) に適用する (方式, 最大行数)
+    pub fn for_path(&self, rel: &Path) -> (Truncate, usize) {
+        self.rule_for(rel).unwrap_or(self.default)
+    }
+
+    /// `[truncate]` のパターンに一致したときだけ `Some`
+    pub fn rule_for(&self, rel: &Path) -> Option<(Truncate, usize)> {
+        let name = rel.file_name().map(Path::new);
+        self.rules
+            .iter()
+            .rev()
+            .find(|(m, ..)| m.is_match(rel) || name.is_some_and(|n| m.is_match(n)))
+            .map(|&(_, strategy, max)| (strategy, max.unwrap_or(self.default.1)))
+    }
+}
+
+/// `total` 行のうち残す (先頭側, 末尾側) の範囲 (0 始まり。使わない側は空)
+pub fn keep(total: usize, max: usize, strategy: Truncate) -> [Range<usize>; 2] {
+    if total <= max {
+        return [0..total, total..total];
+    }
+    match strategy {
+        Truncate::Head => [0..max, total..total],
+        Truncate::Tail => [0..0, total - max..total],
+        Truncate::HeadTail => {
+            let head = max.div_ceil(2);
+            [0..head, total - (max - head)..total]
+        }
+    }
+}
+
+/* --------------------------------------------------------------------- */
+/* tests                                                                 */
+/* --------------------------------------------------------------------- */
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::TruncateRule;
+
+    #[test]
+    fn keeps_head_tail_or_both() {
+        assert_eq!(keep(5, 10, Truncate::Tail), [0..5, 5..5]);
+        assert_eq!(keep(10, 4, Truncate::Head), [0..4, 10..10]);
+        assert_eq!(keep(10, 4, Truncate::Tail), [0..0, 6..10]);
+        assert_eq!(keep(10, 5, Truncate::HeadTail), [0..3, 8..10]);
+    }
+
+    #[test]
+    fn later_rules_win_and_fall_back_to_max_lines() {
+        let config = ConfigParams {
+            max_lines: 100,
+            truncate_rules: vec![
+                TruncateRule {
+                    pattern: "*.log".into(),
+                    strategy: Truncate::Tail,
+                    max_lines: Some(20),
+                },
+                TruncateRule {
+                    pattern: "logs/**".into(),
+                    strategy: Truncate::HeadTail,
+                    max_lines: None,
+                },
+            ],
+            ..ConfigParams::default()
+        };
+        let t = Truncation::new(&config);
+        assert_eq!(t.for_path(Path::new("a/x.log")), (Truncate::Tail, 20));
+        assert_eq!(
+            t.for_path(Path::new("logs/x.log")),
+            (Truncate::HeadTail, 100)
+        );
+        assert_eq!(t.for_path(Path::new("src/lib.rs")), (Truncate::Head, 100));
+    }
+}
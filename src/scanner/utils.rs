@@ -34,33 +34,159 @@ pub fn build_globset(patterns: &[String]) -> Option<GlobSet> {
     }
 
     let mut builder = GlobSetBuilder::new();
-    let mut add = |pat: &str| match Glob::new(pat) {
-        Ok(g) => {
-            builder.add(g); // unify return type to ()
+    for raw in patterns {
+        for glob in expand(raw).into_iter().filter_map(compile) {
+            builder.add(glob);
         }
-        Err(e) => {
-            eprintln!("invalid glob '{}': {}", pat, e);
+    }
+
+    builder.build().ok()
+}
+
+/// `!pattern` で再包含できる順序付きパターン集合 (`[exclude]` / `[skip]` / `[outline]`)
+///
+/// 後に書いた行ほど優先 (last-match-wins)。どの行にも一致しなければ不一致。
+pub struct PatternSet {
+    set: GlobSet,
+    /// glob の番号 → 元の行番号
+    rule_of: Vec<usize>,
+    /// 行ごとの `!` の有無
+    negated: Vec<bool>,
+    /// `!` 行が再包含しうるディレクトリ (枝刈り判定用)
+    reincludes: Vec<Reinclude>,
+}
+
+/// `!` 行の glob がどのディレクトリの中身に届くか
+///
+/// git と同じく、`!*.md` / `!**/README.md` のようにディレクトリを名指ししない行は
+/// 除外されたディレクトリの中までは再包含しない。
+enum Reinclude {
+    /// 先頭がリテラル (`tests/fixtures/README.md` → `tests/fixtures/`)
+    Prefix(String),
+    /// `**/` の後にリテラルのディレクトリ (`**/fixtures/*.md` → `["fixtures"]`)
+    Within(Vec<String>),
+}
+
+impl Reinclude {
+    fn from_glob(pat: &str) -> Option<Self> {
+        let meta = pat.find(['*', '?', '[', '{', '\\']).unwrap_or(pat.len());
+        if meta > 0 {
+            return Some(Self::Prefix(pat[..meta].to_string()));
         }
-    };
+        let mut rest = pat;
+        while let Some(r) = rest.strip_prefix("**/") {
+            rest = r;
+        }
+        let (dir, _) = rest.rsplit_once('/')?;
+        if dir.is_empty() || dir.contains(['*', '?', '[', '{', '\\']) {
+            return None;
+        }
+        Some(Self::Within(dir.split('/').map(str::to_string).collect()))
+    }
 
-    for raw in patterns {
-        if raw.ends_with('/') {
-            let dir = raw.trim_end_matches('/');
-            add(&format!("**/{dir}"));
-            add(&format!("**/{dir}/**"));
-            add(&format!("{dir}/**"));
-        } else if raw.starts_with('.') && !raw.contains('/') && !raw.contains('*') {
-            add(&format!("**/*{raw}"));
-        } else if !raw.contains('/') && !raw.contains('*') {
-            add(&format!("**/{raw}"));
-            add(&format!("**/{raw}/**"));
-            add(&format!("{raw}/**"));
-        } else {
-            add(raw);
+    fn reaches(&self, dir: &Path) -> bool {
+        match self {
+            Self::Prefix(p) => {
+                let dir = format!("{}/", dir.to_string_lossy());
+                p.starts_with(&dir) || dir.starts_with(p.as_str())
+            }
+            Self::Within(want) => {
+                let comps: Vec<String> = dir
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                    .collect();
+                // `dir` の末尾が `want` の先頭に続く (`tests` → `tests/fixtures`)、
+                // または `dir` が `want` の中にある
+                (0..comps.len()).any(|i| {
+                    let tail = &comps[i..];
+                    want.starts_with(tail) || tail.starts_with(want)
+                })
+            }
         }
     }
+}
 
-    builder.build().ok()
+impl PatternSet {
+    /// 最後に一致した行が `!` なしなら `Some(true)`、`!` 付きなら `Some(false)`
+    pub fn matched(&self, path: impl AsRef<Path>) -> Option<bool> {
+        self.set
+            .matches(path)
+            .into_iter()
+            .map(|i| self.rule_of[i])
+            .max()
+            .map(|rule| !self.negated[rule])
+    }
+
+    /// `dir` 配下のどこかが `!` 行で再包含されうるか (されうるなら枝刈りしない)
+    pub fn may_reinclude_under(&self, dir: &Path) -> bool {
+        if dir.as_os_str().is_empty() {
+            return !self.reincludes.is_empty();
+        }
+        self.reincludes.iter().any(|r| r.reaches(dir))
+    }
+}
+
+/// `!` 付きの行を含むパターン列から `PatternSet` を作る
+pub fn build_patterns(patterns: &[String]) -> Option<PatternSet> {
+    if patterns.is_empty() {
+        return None;
+    }
+
+    let mut builder = GlobSetBuilder::new();
+    let mut rule_of = Vec::new();
+    let mut negated = Vec::new();
+    let mut reincludes = Vec::new();
+    for (rule, raw) in patterns.iter().enumerate() {
+        let (neg, pat) = match raw.strip_prefix('!') {
+            Some(rest) => (true, rest.trim_start()),
+            None => (false, raw.as_str()),
+        };
+        negated.push(neg);
+        for pat in expand(pat) {
+            if neg {
+                reincludes.extend(Reinclude::from_glob(&pat));
+            }
+            if let Some(glob) = compile(pat) {
+                builder.add(glob);
+                rule_of.push(rule);
+            }
+        }
+    }
+
+    Some(PatternSet {
+        set: builder.build().ok()?,
+        rule_of,
+        negated,
+        reincludes,
+    })
+}
+
+/// ユーザーパターン 1 行を globset 用の glob 列に展開する
+fn expand(raw: &str) -> Vec<String> {
+    if raw.ends_with('/') {
+        let dir = raw.trim_end_matches('/');
+        vec![
+            format!("**/{dir}"),
+            format!("**/{dir}/**"),
+            format!("{dir}/**"),
+        ]
+    } else if raw.starts_with('.') && !raw.contains('/') && !raw.contains('*') {
+        vec![format!("**/*{raw}")]
+    } else if !raw.contains('/') && !raw.contains('*') {
+        vec![
+            format!("**/{raw}"),
+            format!("**/{raw}/**"),
+            format!("{raw}/**"),
+        ]
+    } else {
+        vec![raw.to_string()]
+    }
+}
+
+fn compile(pat: String) -> Option<Glob> {
+    Glob::new(&pat)
+        .map_err(|e| eprintln!("invalid glob '{}': {}", pat, e))
+        .ok()
 }
 
 #[cfg(test)]
@@ -94,6 +220,44 @@ mod tests {
         assert!(g.is_match(Path::new("nested/Cargo.toml")));
     }
 
+    #[test]
+    fn negation_is_last_match_wins() {
+        let pats: Vec<String> = ["tests/fixtures/", "!tests/fixtures/README.md", "*.md"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let ps = build_patterns(&pats).unwrap();
+        assert_eq!(ps.matched("tests/fixtures/data.json"), Some(true));
+        assert_eq!(
+            ps.matched("tests/fixtures/README.md"),
+            Some(true),
+            "later *.md wins"
+        );
+        let ps = build_patterns(&pats[..2]).unwrap();
+        assert_eq!(ps.matched("tests/fixtures/README.md"), Some(false));
+        assert_eq!(ps.matched("src/lib.rs"), None);
+        assert!(ps.may_reinclude_under(Path::new("tests/fixtures")));
+        assert!(!ps.may_reinclude_under(Path::new("node_modules")));
+    }
+
+    #[test]
+    fn basename_negation_does_not_reach_into_excluded_dirs() {
+        let pats: Vec<String> = [
+            "node_modules/",
+            "!*.md",
+            "!**/README.md",
+            "!**/fixtures/*.json",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+        let ps = build_patterns(&pats).unwrap();
+        assert!(!ps.may_reinclude_under(Path::new("node_modules")));
+        assert!(!ps.may_reinclude_under(Path::new("node_modules/pkg")));
+        assert!(ps.may_reinclude_under(Path::new("tests/fixtures")));
+        assert!(ps.may_reinclude_under(Path::new("tests/fixtures/deep")));
+    }
+
     #[test]
     fn plain_name_dir() {
         let g = gs(&["node_modules"]);
@@ -1,33 +1,61 @@
 // src/scanner/walker.rs
 
-use globset::GlobSet;
+use crate::gitignore::GitIgnore;
+use crate::scanner::scope::Scopes;
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 use walkdir::{DirEntry, WalkDir};
 
-/// Collect directory or file entries, honoring `exclude_globset`.
+/// Collect directory or file entries, honoring `[exclude]` of every `.gather`
+/// in scope and (when given) `.gitignore` rules. Ignored directories are
+/// pruned unless a `!pattern` may re-include something beneath them; an
+/// excluded directory is kept only as the parent of a re-included entry.
 pub fn collect_entries(
     target_dir: &Path,
-    exclude_globset: &Option<GlobSet>,
+    scopes: &Scopes,
+    gitignore: Option<&GitIgnore>,
     files_only: bool,
 ) -> Vec<DirEntry> {
-    let matches_exclude = |entry: &DirEntry| -> bool {
-        if let Some(gs) = exclude_globset {
-            let rel: PathBuf = entry
-                .path()
-                .strip_prefix(target_dir)
-                .unwrap_or(entry.path())
-                .into();
-            gs.is_match(&rel)
-        } else {
-            false
-        }
+    let rel = |entry: &DirEntry| -> PathBuf {
+        entry
+            .path()
+            .strip_prefix(target_dir)
+            .unwrap_or(entry.path())
+            .into()
+    };
+    let matches_exclude = |entry: &DirEntry| -> bool { scopes.is_excluded(&rel(entry)) };
+    let prunes_exclude = |entry: &DirEntry| -> bool {
+        entry.file_type().is_dir()
+            && matches_exclude(entry)
+            && !scopes.may_reinclude_under(&rel(entry))
+    };
+    let matches_gitignore = |entry: &DirEntry| -> bool {
+        entry.depth() > 0
+            && gitignore.is_some_and(|gi| gi.is_ignored(entry.path(), entry.file_type().is_dir()))
     };
 
-    WalkDir::new(target_dir)
+    let walked: Vec<(DirEntry, bool)> = WalkDir::new(target_dir)
         .into_iter() // << fix: call into_iter() first
-        .filter_entry(|e| !(e.file_type().is_dir() && matches_exclude(e)))
+        .filter_entry(|e| !prunes_exclude(e) && !matches_gitignore(e))
         .flatten()
-        .filter(|e| !matches_exclude(e)) // drop excluded files & dirs
+        .map(|e| {
+            let excluded = matches_exclude(&e);
+            (e, excluded)
+        })
+        .collect();
+
+    // 除外ディレクトリでも、再包含されたファイルの親なら残す
+    let parents: HashSet<PathBuf> = walked
+        .iter()
+        .filter(|(e, excluded)| !excluded && e.file_type().is_file())
+        .flat_map(|(e, _)| e.path().ancestors().skip(1))
+        .map(Path::to_path_buf)
+        .collect();
+
+    walked
+        .into_iter()
+        .filter(|(e, excluded)| !excluded || parents.contains(e.path()))
+        .map(|(e, _)| e)
         .filter(|e| !files_only || e.file_type().is_file())
         .collect()
 }
@@ -38,10 +66,18 @@ pub fn collect_entries(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::scanner::utils::build_globset;
+    use crate::model::ConfigParams;
     use std::fs::{self, File};
     use tempfile::tempdir;
 
+    fn excluding(root: &Path, pats: &[&str]) -> Scopes {
+        let cfg = ConfigParams {
+            exclude_patterns: pats.iter().map(|p| p.to_string()).collect(),
+            ..ConfigParams::default()
+        };
+        Scopes::new(root, &cfg)
+    }
+
     #[test]
     fn exclude_dir_is_skipped() {
         let dir = tempdir().unwrap();
@@ -51,8 +87,7 @@ mod tests {
         File::create(root.join("keep/file")).unwrap();
         File::create(root.join("skip/file")).unwrap();
 
-        let gs = build_globset(&["skip/".to_string()]).unwrap();
-        let entries = collect_entries(root, &Some(gs), true);
+        let entries = collect_entries(root, &excluding(root, &["skip/"]), None, true);
         let paths: Vec<_> = entries
             .iter()
             .map(|e| e.path().strip_prefix(root).unwrap().to_path_buf())
@@ -67,12 +102,76 @@ mod tests {
         File::create(root.join("Cargo.lock")).unwrap();
         File::create(root.join("Cargo.toml")).unwrap();
 
-        let gs = build_globset(&["Cargo.lock".to_string()]).unwrap();
-        let entries = collect_entries(root, &Some(gs), true);
+        let entries = collect_entries(root, &excluding(root, &["Cargo.lock"]), None, true);
         let paths: Vec<_> = entries
             .iter()
             .map(|e| e.path().strip_prefix(root).unwrap().to_path_buf())
             .collect();
         assert_eq!(paths, vec![PathBuf::from("Cargo.toml")]);
     }
+
+    #[test]
+    fn gitignored_dir_is_pruned_but_negated_file_kept() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("target/debug")).unwrap();
+        File::create(root.join("target/debug/app")).unwrap();
+        File::create(root.join("a.log")).unwrap();
+        File::create(root.join("keep.log")).unwrap();
+        fs::write(root.join(".gitignore"), "target/\n*.log\n!keep.log\n").unwrap();
+
+        let gi = GitIgnore::new(root);
+        let entries = collect_entries(root, &excluding(root, &[]), Some(&gi), true);
+        let mut paths: Vec<_> = entries
+            .iter()
+            .map(|e| e.path().strip_prefix(root).unwrap().to_path_buf())
+            .collect();
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec![PathBuf::from(".gitignore"), PathBuf::from("keep.log")]
+        );
+    }
+
+    #[test]
+    fn negated_file_keeps_excluded_parent_in_tree() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("tests/fixtures")).unwrap();
+        File::create(root.join("tests/fixtures/README.md")).unwrap();
+        File::create(root.join("tests/fixtures/data.json")).unwrap();
+
+        let pats = ["tests/fixtures/", "!tests/fixtures/README.md"];
+        let entries = collect_entries(root, &excluding(root, &pats), None, false);
+        let paths: Vec<_> = entries
+            .iter()
+            .map(|e| e.path().strip_prefix(root).unwrap().to_path_buf())
+            .collect();
+        assert_eq!(
+            paths,
+            vec![
+                PathBuf::from(""),
+                PathBuf::from("tests"),
+                PathBuf::from("tests/fixtures"),
+                PathBuf::from("tests/fixtures/README.md"),
+            ]
+        );
+    }
+
+    #[test]
+    fn basename_negation_keeps_excluded_dir_pruned() {
+        let dir = tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("node_modules/pkg")).unwrap();
+        File::create(root.join("node_modules/pkg/README.md")).unwrap();
+        File::create(root.join("README.md")).unwrap();
+
+        let pats = ["node_modules/", "*.md", "!*.md"];
+        let entries = collect_entries(root, &excluding(root, &pats), None, false);
+        let paths: Vec<_> = entries
+            .iter()
+            .map(|e| e.path().strip_prefix(root).unwrap().to_path_buf())
+            .collect();
+        assert_eq!(paths, vec![PathBuf::from(""), PathBuf::from("README.md")]);
+    }
 }
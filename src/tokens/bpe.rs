@@ -0,0 +1,231 @@
+//! src/tokens/bpe.rs
+//!
+//! tiktoken 互換の BPE カウンタ
+//!
+//! - 語彙ファイルは tiktoken 形式 (`<base64 トークン> <rank>` を 1 行ずつ)。
+//!   `cl100k_base.tiktoken` などをそのまま指定できる。
+//! - 事前分割は cl100k_base の正規表現を手書きで再現 (`regex` は先読みが使えないため)。
+//! - 各ピースは rank の小さいペアから順に結合し、残った部分の数をトークン数とする。
+//!   部分は連結リストで持ち、ペアの rank はヒープから取り出す (結合ごとに両隣のペアだけ再計算)。
+
+use anyhow::Context;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::fs;
+use std::path::Path;
+
+pub struct Bpe {
+    ranks: HashMap<Vec<u8>, u32>,
+}
+
+impl Bpe {
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        let mut ranks = HashMap::new();
+        for (no, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let parsed = line.split_once(' ').and_then(|(tok, rank)| {
+                Some((STANDARD.decode(tok).ok()?, rank.trim().parse().ok()?))
+            });
+            let (tok, rank) = parsed.with_context(|| format!("line {}: invalid entry", no + 1))?;
+            ranks.insert(tok, rank);
+        }
+        anyhow::ensure!(!ranks.is_empty(), "empty vocabulary");
+        Ok(Self { ranks })
+    }
+
+    pub fn count(&self, text: &str) -> usize {
+        pieces(text)
+            .into_iter()
+            .map(|p| self.count_piece(p.as_bytes()))
+            .sum()
+    }
+
+    fn count_piece(&self, piece: &[u8]) -> usize {
+        if piece.len() <= 1 || self.ranks.contains_key(piece) {
+            return piece.len().min(1);
+        }
+        // 部分 i は piece[i..next[i]]。結合された側は alive[i] = false
+        let n = piece.len();
+        let mut next: Vec<usize> = (1..=n).collect();
+        let mut prev: Vec<Option<usize>> = (0..n).map(|i| i.checked_sub(1)).collect();
+        let mut alive = vec![true; n];
+        let pair_rank = |i: usize, next: &[usize]| {
+            let j = next[i];
+            (j < n)
+                .then(|| self.ranks.get(&piece[i..next[j]]).copied())
+                .flatten()
+        };
+
+        let mut heap: BinaryHeap<Reverse<(u32, usize)>> = (0..n)
+            .filter_map(|i| pair_rank(i, &next).map(|r| Reverse((r, i))))
+            .collect();
+        let mut parts = n;
+        while let Some(Reverse((rank, i))) = heap.pop() {
+            // 結合済み・相方が変わったペアの古いエントリは読み飛ばす
+            if !alive[i] || pair_rank(i, &next) != Some(rank) {
+                continue;
+            }
+            let j = next[i];
+            alive[j] = false;
+            next[i] = next[j];
+            if next[i] < n {
+                prev[next[i]] = Some(i);
+            }
+            parts -= 1;
+            for k in [prev[i], Some(i)].into_iter().flatten() {
+                if let Some(r) = pair_rank(k, &next) {
+                    heap.push(Reverse((r, k)));
+                }
+            }
+        }
+        parts
+    }
+}
+
+/* ---------------- 事前分割 (cl100k_base 相当) ---------------- */
+
+/// ```text
+/// (?i:'s|'t|'re|'ve|'m|'ll|'d) | [^\r\n\p{L}\p{N}]?\p{L}+ | \p{N}{1,3}
+///   | ?[^\s\p{L}\p{N}]+[\r\n]* | \s*[\r\n]+ | \s+(?!\S) | \s+
+/// ```
+fn pieces(text: &str) -> Vec<&str> {
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let cs: Vec<char> = chars.iter().map(|&(_, c)| c).collect();
+    let byte = |i: usize| chars.get(i).map_or(text.len(), |&(b, _)| b);
+
+    let mut out = Vec::new();
+    let mut i = 0;
+    while i < cs.len() {
+        let len = match_at(&cs, i);
+        out.push(&text[byte(i)..byte(i + len)]);
+        i += len;
+    }
+    out
+}
+
+/// 位置 `i` から一致する文字数 (常に 1 以上)
+fn match_at(c: &[char], i: usize) -> usize {
+    let letter = |ch: &char| ch.is_alphabetic();
+    let number = |ch: &char| ch.is_numeric();
+    let newline = |ch: &char| matches!(ch, '\r' | '\n');
+    let other = |ch: &char| !ch.is_whitespace() && !letter(ch) && !number(ch);
+    let run = |from: usize, pred: &dyn Fn(&char) -> bool| {
+        c[from..].iter().take_while(|ch| pred(ch)).count()
+    };
+
+    /* 'll などの短縮形 */
+    if c[i] == '\'' {
+        for suffix in ["s", "t", "re", "ve", "m", "ll", "d"] {
+            let n = suffix.len();
+            let tail: String = c[i + 1..].iter().take(n).collect();
+            if tail.eq_ignore_ascii_case(suffix) {
+                return 1 + n;
+            }
+        }
+    }
+
+    /* 記号 1 文字 + 文字列 */
+    let start = if !newline(&c[i])
+        && !letter(&c[i])
+        && !number(&c[i])
+        && c.get(i + 1).is_some_and(letter)
+    {
+        i + 1
+    } else {
+        i
+    };
+    if c.get(start).is_some_and(letter) {
+        return start - i + run(start, &letter);
+    }
+
+    /* 数字は 3 桁ずつ */
+    if number(&c[i]) {
+        return run(i, &number).min(3);
+    }
+
+    /* 空白 1 つ + 記号列 + 改行 */
+    let start = if c[i] == ' ' && c.get(i + 1).is_some_and(other) {
+        i + 1
+    } else {
+        i
+    };
+    if c.get(start).is_some_and(other) {
+        let end = start + run(start, &other);
+        return end - i + run(end, &newline);
+    }
+
+    /* 空白: 改行までを含む塊 → 次の単語の前の 1 文字を残す → 残り全部 */
+    let ws = run(i, &|ch: &char| ch.is_whitespace());
+    if let Some(last_nl) = c[i..i + ws].iter().rposition(newline) {
+        return last_nl + 1;
+    }
+    if i + ws < c.len() && ws > 1 {
+        return ws - 1;
+    }
+    ws
+}
+
+/* ------------------------------------------------------------------ */
+/* tests                                                              */
+/* ------------------------------------------------------------------ */
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn pre_tokenizer_matches_cl100k_rules() {
+        assert_eq!(
+            pieces("Hello world's  \n\n x1234567"),
+            vec!["Hello", " world", "'s", "  \n\n", " x", "123", "456", "7"]
+        );
+        assert_eq!(pieces("fn main() {}"), vec!["fn", " main", "()", " {}"]);
+        assert_eq!(pieces("a  b"), vec!["a", " ", " b"]);
+    }
+
+    #[test]
+    fn merges_by_rank_from_tiktoken_file() {
+        let mut vocab = NamedTempFile::new().unwrap();
+        for (rank, tok) in ["h", "e", "l", "o", " ", "he", "ll", "hell"]
+            .iter()
+            .enumerate()
+        {
+            writeln!(vocab, "{} {rank}", STANDARD.encode(tok)).unwrap();
+        }
+        let bpe = Bpe::load(vocab.path()).unwrap();
+
+        // "hello" → he + ll → hell + o
+        assert_eq!(bpe.count("hello"), 2);
+        // " hello" → " " + hell + o
+        assert_eq!(bpe.count("hello hello"), 5);
+        assert_eq!(bpe.count(""), 0);
+    }
+
+    #[test]
+    fn long_pieces_merge_in_rank_order() {
+        let mut vocab = NamedTempFile::new().unwrap();
+        for (rank, tok) in ["a", "b", "aa", "aaaa", "ab"].iter().enumerate() {
+            writeln!(vocab, "{} {rank}", STANDARD.encode(tok)).unwrap();
+        }
+        let bpe = Bpe::load(vocab.path()).unwrap();
+
+        // aa が ab より先に結合される: "aab" → aa + b
+        assert_eq!(bpe.count_piece(b"aab"), 2);
+        assert_eq!(bpe.count_piece(b"aaab"), 2, "aa + ab");
+        assert_eq!(bpe.count_piece(&[b'a'; 100_000]), 25_000);
+    }
+
+    #[test]
+    fn invalid_vocab_is_an_error() {
+        let mut vocab = NamedTempFile::new().unwrap();
+        writeln!(vocab, "not-base64!! x").unwrap();
+        assert!(Bpe::load(vocab.path()).is_err());
+    }
+}
@@ -0,0 +1,86 @@
+//! src/tokens/mod.rs
+//!
+//! トークン数の見積もり
+//!
+//! ```text
+//!  token_vocab あり → bpe::Bpe (tiktoken 形式の語彙ファイルを読み込む BPE)
+//!  token_vocab なし → chars/4 の簡易見積もり
+//! ```
+//! 語彙ファイルはネットワークから取得せず、ローカルのファイルだけを使う。
+
+mod bpe;
+
+use crate::model::ConfigParams;
+use bpe::Bpe;
+use std::path::Path;
+
+/// トークンカウンタ
+pub enum TokenCounter {
+    /// 語彙ファイルによる BPE (cl100k_base などと互換)
+    Bpe(Bpe),
+    /// 4 文字 ≒ 1 トークンの見積もり
+    Approx,
+}
+
+impl TokenCounter {
+    /// 設定の `token_vocab` を読み込む。未指定・読み込み失敗時は `Approx`。
+    /// 相対パスは対象ディレクトリ基準。
+    pub fn from_config(cfg: &ConfigParams, base: &Path) -> Self {
+        let Some(vocab) = &cfg.token_vocab else {
+            return TokenCounter::Approx;
+        };
+        let path = base.join(vocab);
+        match Bpe::load(&path) {
+            Ok(bpe) => TokenCounter::Bpe(bpe),
+            Err(e) => {
+                eprintln!(
+                    "token_vocab を読み込めません ({}): {e} – chars/4 で見積もります",
+                    path.display()
+                );
+                TokenCounter::Approx
+            }
+        }
+    }
+
+    pub fn count(&self, text: &str) -> usize {
+        match self {
+            TokenCounter::Bpe(bpe) => bpe.count(text),
+            TokenCounter::Approx => text.chars().count().div_ceil(4),
+        }
+    }
+
+    /// サマリー表示用の方式名
+    pub fn method(&self) -> &'static str {
+        match self {
+            TokenCounter::Bpe(_) => "BPE",
+            TokenCounter::Approx => "estimated, chars/4",
+        }
+    }
+}
+
+/* ------------------------------------------------------------------ */
+/* tests                                                              */
+/* ------------------------------------------------------------------ */
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn approx_rounds_up_by_chars() {
+        let c = TokenCounter::Approx;
+        assert_eq!(c.count(""), 0);
+        assert_eq!(c.count("abcd"), 1);
+        assert_eq!(c.count("abcde"), 2);
+        assert_eq!(c.count("日本語です"), 2, "counts chars, not bytes");
+    }
+
+    #[test]
+    fn missing_vocab_falls_back_to_approx() {
+        let cfg = ConfigParams {
+            token_vocab: Some("does/not/exist.tiktoken".into()),
+            ..ConfigParams::default()
+        };
+        let c = TokenCounter::from_config(&cfg, Path::new("."));
+        assert!(matches!(c, TokenCounter::Approx));
+    }
+}
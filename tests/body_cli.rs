@@ -0,0 +1,73 @@
+//! 本文の描画 (行番号・打ち切り) の E2E テスト
+//!
+//! - `--line-numbers` / `line_numbers = yes` で各行に右寄せの行番号が付く
+//! - `max_lines` で打ち切っても番号は元ファイルの行番号のまま
+//! - `truncate = tail / head_tail` と `[truncate]` のパターン別設定
+
+use assert_cmd::Command;
+use std::fs;
+use tempfile::tempdir;
+
+#[test]
+fn line_numbers_are_right_aligned_and_survive_truncation() {
+    let tmp = tempdir().unwrap();
+    let root = tmp.path();
+    let body: String = (1..=12).map(|i| format!("line {i}\n")).collect();
+    fs::write(root.join("a.txt"), body).unwrap();
+    fs::write(
+        root.join(".gather"),
+        "[settings]\nmax_lines = 10\n\n[exclude]\ngather/\n.gather\n",
+    )
+    .unwrap();
+
+    Command::cargo_bin("gather")
+        .unwrap()
+        .current_dir(root)
+        .args(["--no-open", "--line-numbers", "."])
+        .assert()
+        .success();
+
+    let out = fs::read_to_string(root.join("gather/output.txt")).unwrap();
+    assert!(out.contains("```text\n 1 | line 1\n 2 | line 2\n"), "{out}");
+    assert!(
+        out.contains("10 | line 10\n... (省略: 2 行) ...\n```"),
+        "{out}"
+    );
+    assert!(!out.contains("line 11"));
+}
+
+#[test]
+fn head_tail_and_per_pattern_truncation() {
+    let tmp = tempdir().unwrap();
+    let root = tmp.path();
+    let body: String = (1..=10).map(|i| format!("line {i}\n")).collect();
+    fs::write(root.join("a.txt"), &body).unwrap();
+    fs::write(root.join("app.log"), &body).unwrap();
+    fs::write(
+        root.join(".gather"),
+        "[settings]\nmax_lines = 4\ntruncate = head_tail\n\n[exclude]\ngather/\n.gather\n\n\
+         [truncate]\n*.log = tail 3\n",
+    )
+    .unwrap();
+
+    Command::cargo_bin("gather")
+        .unwrap()
+        .current_dir(root)
+        .args(["--no-open", "--line-numbers", "."])
+        .assert()
+        .success();
+
+    let out = fs::read_to_string(root.join("gather/output.txt")).unwrap();
+    assert!(
+        out.contains(
+            "### a.txt\n```text\n 1 | line 1\n 2 | line 2\n... (省略: 6 行) ...\n 9 | line 9\n10 | line 10\n```"
+        ),
+        "{out}"
+    );
+    assert!(
+        out.contains(
+            "### app.log\n```\n... (省略: 7 行) ...\n 8 | line 8\n 9 | line 9\n10 | line 10\n```"
+        ),
+        "{out}"
+    );
+}
@@ -0,0 +1,91 @@
+//! `--max-tokens` による予算縮退の E2E テスト
+//!
+//! - 予算を超えると優先度の低いファイルから outline → tree-only に縮退する
+//! - 縮退したファイルはツリーに `[omitted:budget-outline]` / `[omitted:budget]` と注記される
+//! - `--tree-tokens` の注記を含めても合計が上限に収まる
+
+use assert_cmd::Command;
+use std::fs;
+use tempfile::tempdir;
+
+#[test]
+fn max_tokens_degrades_low_priority_files() {
+    let tmp = tempdir().unwrap();
+    let root = tmp.path();
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(root.join("src/lib.rs"), "pub fn keep_me() {}\n").unwrap();
+    let body: String = (0..200).map(|i| format!("    let v{i} = {i};\n")).collect();
+    fs::write(
+        root.join("src/heavy.rs"),
+        format!("pub fn heavy() {{\n{body}}}\n"),
+    )
+    .unwrap();
+    fs::write(root.join("data.txt"), "z".repeat(4000)).unwrap();
+    fs::write(
+        root.join(".gather"),
+        "[exclude]\ngather/\n.gather\n\n[priority]\nsrc/lib.rs\n",
+    )
+    .unwrap();
+
+    Command::cargo_bin("gather")
+        .unwrap()
+        .current_dir(root)
+        .args(["--no-open", "--max-tokens", "300", "."])
+        .assert()
+        .success();
+
+    let out = fs::read_to_string(root.join("gather/output.txt")).unwrap();
+    assert!(
+        out.contains("heavy.rs   [omitted:budget-outline]"),
+        "got:\n{out}"
+    );
+    assert!(out.contains("data.txt   [omitted:budget]"), "got:\n{out}");
+    assert!(out.contains("**fn** heavy"), "heavy.rs is outlined");
+    assert!(!out.contains("let v199"), "heavy.rs body is dropped");
+    assert!(!out.contains("zzzz"), "data.txt body is dropped");
+    assert!(
+        out.contains("pub fn keep_me() {}"),
+        "priority file keeps its body"
+    );
+}
+
+#[test]
+fn tree_token_notes_count_against_the_budget() {
+    let tmp = tempdir().unwrap();
+    let root = tmp.path();
+    for d in 0..4 {
+        let dir = root.join(format!("pkg{d}/src"));
+        fs::create_dir_all(&dir).unwrap();
+        for f in 0..5 {
+            fs::write(dir.join(format!("file{f}.txt")), "word ".repeat(40)).unwrap();
+        }
+    }
+    fs::write(root.join(".gather"), "[exclude]\ngather/\n.gather\n").unwrap();
+
+    let limit = 1000;
+    let assert = Command::cargo_bin("gather")
+        .unwrap()
+        .current_dir(root)
+        .args([
+            "--no-open",
+            "--tree-tokens",
+            "--max-tokens",
+            &limit.to_string(),
+        ])
+        .arg(".")
+        .assert()
+        .success();
+
+    let stderr = String::from_utf8_lossy(&assert.get_output().stderr).to_string();
+    let total: usize = stderr
+        .lines()
+        .find_map(|l| l.trim().strip_prefix("- Tokens: "))
+        .and_then(|rest| rest.split_whitespace().next())
+        .and_then(|n| n.parse().ok())
+        .unwrap_or_else(|| panic!("no token total in:\n{stderr}"));
+    assert!(total <= limit, "total {total} exceeds {limit}");
+
+    let out = fs::read_to_string(root.join("gather/output.txt")).unwrap();
+    assert!(out.contains("[tokens:"), "got:\n{out}");
+    assert!(out.contains("[omitted:budget]"), "got:\n{out}");
+}
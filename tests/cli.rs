@@ -38,3 +38,27 @@ fn first_run_creates_gather_and_succeeds() {
         gather_dir
     );
 }
+
+/// `.gather` の不正な値・未知のキーは行番号付きで警告され、実行は続く
+#[test]
+fn config_diagnostics_are_reported_with_line_numbers() {
+    let tmp = tempdir().unwrap();
+    let root = tmp.path();
+    fs::write(root.join("a.txt"), "hello").unwrap();
+    fs::write(
+        root.join(".gather"),
+        "[settings]\nmax_lines = abc\nmaxlines = 3\n\n[exclude]\ngather/\n.gather\n",
+    )
+    .unwrap();
+
+    Command::cargo_bin("gather")
+        .unwrap()
+        .current_dir(root)
+        .args(["--no-open", "."])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains(".gather:2: [settings] max_lines"))
+        .stderr(predicate::str::contains(
+            ".gather:3: [settings] maxlines: unknown field",
+        ));
+}
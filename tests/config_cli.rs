@@ -0,0 +1,67 @@
+//! `gather config` が実効設定と各値の出どころ (default / .gather line N / CLI)
+//! を表示し、出力ファイルは作らないことを確認する。
+
+use assert_cmd::Command;
+use predicates::prelude::*;
+use serde_json::Value;
+use std::fs;
+use tempfile::tempdir;
+
+fn project() -> tempfile::TempDir {
+    let tmp = tempdir().unwrap();
+    fs::write(
+        tmp.path().join(".gather"),
+        "[settings]\nmax_lines = 20\nskip_binary = true\n\n[exclude]\nbuild/\n",
+    )
+    .unwrap();
+    tmp
+}
+
+#[test]
+fn json_reports_value_sources() {
+    let tmp = project();
+    let root = tmp.path();
+
+    let out = Command::cargo_bin("gather")
+        .unwrap()
+        .current_dir(root)
+        .args(["config", ".", "-m", "50", "-p", "*.tmp", "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(out.status.success());
+    let doc: Value = serde_json::from_slice(&out.stdout).unwrap();
+    let values = &doc["values"];
+
+    assert_eq!(values["max_lines"]["value"], 50);
+    assert_eq!(values["max_lines"]["source"]["kind"], "cli");
+    assert_eq!(values["skip_binary"]["value"], true);
+    assert_eq!(
+        values["skip_binary"]["source"],
+        serde_json::json!({"kind": "gather", "line": 3})
+    );
+    assert_eq!(values["exclude_patterns"][0]["value"], "build/");
+    assert_eq!(values["exclude_patterns"][0]["source"]["line"], 6);
+    assert_eq!(values["exclude_patterns"][1]["value"], "*.tmp");
+    assert_eq!(values["exclude_patterns"][1]["source"]["kind"], "cli");
+    assert_eq!(values["token_top_n"]["source"]["kind"], "default");
+
+    // 設定を見るだけ – 出力ディレクトリは作らない
+    assert!(!root.join("gather").exists());
+}
+
+#[test]
+fn text_lists_gitignore_files() {
+    let tmp = project();
+    let root = tmp.path();
+    fs::write(root.join(".gitignore"), "target/\n").unwrap();
+
+    Command::cargo_bin("gather")
+        .unwrap()
+        .current_dir(root)
+        .args(["config", ".", "--use-gitignore"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match(r"max_lines\s+= 20\s+# \.gather line 2").unwrap())
+        .stdout(predicate::str::is_match(r"use_gitignore\s+= true\s+# CLI").unwrap())
+        .stdout(predicate::str::is_match(r"\.gitignore\s+# \.gitignore").unwrap());
+}
@@ -0,0 +1,194 @@
+//! `--output-format` / `output_format` の E2E テスト
+//!
+//! - xml では `<documents><document index="n">…` 形式で、中身はエスケープされる
+//! - json / jsonl ではヘッダ (ツリー・サマリー) と 1 ファイル 1 オブジェクトを出力
+//! - markdown のフェンスは中身のバッククォート列より長くなり、言語タグが付く (`[languages]` で上書き)
+//! - 既定の拡張子は形式ごと (`.xml` / `.json` / `.jsonl`)
+
+use assert_cmd::Command;
+use std::fs;
+use tempfile::tempdir;
+
+#[test]
+fn xml_output_wraps_documents_and_escapes() {
+    let tmp = tempdir().unwrap();
+    let root = tmp.path();
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(root.join("src/a.rs"), "fn f() -> Vec<u8> { a && b }\n").unwrap();
+    fs::write(root.join("README.md"), "# Title\n").unwrap();
+    fs::write(root.join(".gather"), "[exclude]\ngather/\n.gather\n").unwrap();
+
+    Command::cargo_bin("gather")
+        .unwrap()
+        .current_dir(root)
+        .args(["--no-open", "--output-format", "xml", "."])
+        .assert()
+        .success();
+
+    let out = fs::read_to_string(root.join("gather/output.xml")).unwrap();
+    assert!(out.starts_with("<directory_tree>\n"), "{out}");
+    assert!(out.contains(
+        "<document index=\"2\">\n<source>src/a.rs</source>\n<document_content>\n\
+         fn f() -&gt; Vec&lt;u8&gt; { a &amp;&amp; b }\n</document_content>\n</document>\n"
+    ));
+    assert!(out.ends_with("</documents>\n"));
+    assert!(!out.contains("```"), "no markdown fences");
+}
+
+#[test]
+fn output_format_setting_is_read_from_gather_file() {
+    let tmp = tempdir().unwrap();
+    let root = tmp.path();
+    fs::write(root.join("a.txt"), "hello\n").unwrap();
+    fs::write(
+        root.join(".gather"),
+        "[settings]\noutput_format = xml\n\n[exclude]\ngather/\n.gather\n",
+    )
+    .unwrap();
+
+    Command::cargo_bin("gather")
+        .unwrap()
+        .current_dir(root)
+        .args(["--no-open", "."])
+        .assert()
+        .success();
+
+    let out = fs::read_to_string(root.join("gather/output.xml")).unwrap();
+    assert!(out.contains("<source>a.txt</source>"), "{out}");
+}
+
+#[test]
+fn jsonl_output_has_header_and_file_records() {
+    let tmp = tempdir().unwrap();
+    let root = tmp.path();
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(root.join("src/lib.rs"), "pub fn add() {}\n").unwrap();
+    fs::write(root.join("src/big.rs"), "pub fn big() {}\n").unwrap();
+    fs::write(root.join("notes.log"), "a\nb\nc\n").unwrap();
+    fs::write(
+        root.join(".gather"),
+        "[settings]\nmax_lines = 2\n\n[exclude]\ngather/\n.gather\n\n[skip]\n*.md\n\n[outline]\nsrc/big.rs\n",
+    )
+    .unwrap();
+    fs::write(root.join("README.md"), "# skipped\n").unwrap();
+
+    Command::cargo_bin("gather")
+        .unwrap()
+        .current_dir(root)
+        .args(["--no-open", "--output-format", "jsonl", "."])
+        .assert()
+        .success();
+
+    let out = fs::read_to_string(root.join("gather/output.jsonl")).unwrap();
+    let records: Vec<serde_json::Value> = out
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(records[0]["type"], "header");
+    assert_eq!(records[0]["summary"]["files"]["processed"], 3);
+    assert!(records[0]["tree"].as_str().unwrap().contains("README.md"));
+
+    let file = |path: &str| {
+        records
+            .iter()
+            .find(|r| r["path"] == path)
+            .unwrap_or_else(|| panic!("{path} missing in:\n{out}"))
+    };
+    let log = file("notes.log");
+    assert_eq!(log["content"], "a\nb\n... (省略: 1 行) ...\n");
+    assert_eq!(log["truncated"], true);
+    assert_eq!(log["lines"], 3);
+
+    let big = file("src/big.rs");
+    assert_eq!(big["omitted"], "outline");
+    assert_eq!(big["symbols"][0]["ident"], "big");
+    assert_eq!(big["language"], "rust");
+
+    let readme = file("README.md");
+    assert_eq!(readme["omitted"], "pattern");
+    assert!(readme["content"].is_null());
+}
+
+#[test]
+fn json_output_is_one_document() {
+    let tmp = tempdir().unwrap();
+    let root = tmp.path();
+    fs::write(root.join("a.txt"), "hello\n").unwrap();
+    fs::write(root.join("b.txt"), "world\n").unwrap();
+    fs::write(root.join(".gather"), "[exclude]\ngather/\n.gather\n").unwrap();
+
+    Command::cargo_bin("gather")
+        .unwrap()
+        .current_dir(root)
+        .args(["--no-open", "--output-format", "json", "."])
+        .assert()
+        .success();
+
+    let out = fs::read_to_string(root.join("gather/output.json")).unwrap();
+    let v: serde_json::Value = serde_json::from_str(&out).unwrap();
+    assert_eq!(v["files"].as_array().unwrap().len(), 2);
+    assert_eq!(v["files"][1]["content"], "world\n");
+    assert_eq!(
+        v["header"]["summary"]["tokens"]["method"],
+        "estimated, chars/4"
+    );
+}
+
+#[test]
+fn markdown_fences_outgrow_backticks_in_bodies_and_outlines() {
+    let tmp = tempdir().unwrap();
+    let root = tmp.path();
+    fs::write(root.join("README.md"), "```sh\ncargo run\n```\n").unwrap();
+    fs::write(
+        root.join("lib.rs"),
+        "/// ```\n/// assert!(ok());\n/// ```\npub fn ok() -> bool { true }\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join(".gather"),
+        "[settings]\noutline_docs = full\n\n[exclude]\ngather/\n.gather\n\n[outline]\nlib.rs\n",
+    )
+    .unwrap();
+
+    Command::cargo_bin("gather")
+        .unwrap()
+        .current_dir(root)
+        .args(["--no-open", "."])
+        .assert()
+        .success();
+
+    let out = fs::read_to_string(root.join("gather/output.txt")).unwrap();
+    assert!(
+        out.contains("### README.md\n````markdown\n```sh\ncargo run\n```\n````\n"),
+        "{out}"
+    );
+    assert!(out.contains("### lib.rs\n````\n"), "{out}");
+}
+
+#[test]
+fn markdown_fences_carry_language_tags_with_overrides() {
+    let tmp = tempdir().unwrap();
+    let root = tmp.path();
+    fs::write(root.join("main.rs"), "fn main() {}\n").unwrap();
+    fs::write(root.join("Dockerfile"), "FROM rust\n").unwrap();
+    fs::write(root.join("page.tpl"), "<p>{{x}}</p>\n").unwrap();
+    fs::write(root.join("notes.xyz"), "?\n").unwrap();
+    fs::write(
+        root.join(".gather"),
+        "[exclude]\ngather/\n.gather\n\n[languages]\n*.tpl = html\n",
+    )
+    .unwrap();
+
+    Command::cargo_bin("gather")
+        .unwrap()
+        .current_dir(root)
+        .args(["--no-open", "."])
+        .assert()
+        .success();
+
+    let out = fs::read_to_string(root.join("gather/output.txt")).unwrap();
+    assert!(out.contains("### main.rs\n```rust\n"), "{out}");
+    assert!(out.contains("### Dockerfile\n```dockerfile\n"), "{out}");
+    assert!(out.contains("### page.tpl\n```html\n"), "{out}");
+    assert!(out.contains("### notes.xyz\n```\n"), "unknown stays bare");
+}
@@ -0,0 +1,47 @@
+//! Integration test – `--use-gitignore` は git と同じ意味論で走査時に適用される
+
+use assert_cmd::Command;
+use std::fs;
+use tempfile::tempdir;
+
+#[test]
+fn nested_gitignore_negation_anchoring_and_info_exclude() {
+    let tmp = tempdir().unwrap();
+    let root = tmp.path();
+
+    fs::create_dir_all(root.join(".git/info")).unwrap();
+    fs::create_dir_all(root.join("src/build")).unwrap();
+    fs::create_dir_all(root.join("build")).unwrap();
+    fs::create_dir_all(root.join("logs")).unwrap();
+    fs::write(root.join(".git/info/exclude"), "*.tmp\n").unwrap();
+    fs::write(root.join(".gitignore"), "/build\n*.log\n!keep.log\n").unwrap();
+    fs::write(root.join("logs/.gitignore"), "!*.log\nsecret.txt\n").unwrap();
+    fs::write(root.join(".gather"), "[exclude]\ngather/\n.gather\n").unwrap();
+
+    fs::write(root.join("build/out.rs"), "ROOT_BUILD\n").unwrap();
+    fs::write(root.join("src/build/gen.rs"), "NESTED_BUILD\n").unwrap();
+    fs::write(root.join("a.log"), "ROOT_LOG\n").unwrap();
+    fs::write(root.join("keep.log"), "KEPT_LOG\n").unwrap();
+    fs::write(root.join("logs/app.log"), "NESTED_LOG\n").unwrap();
+    fs::write(root.join("logs/secret.txt"), "SECRET\n").unwrap();
+    fs::write(root.join("scratch.tmp"), "SCRATCH\n").unwrap();
+
+    Command::cargo_bin("gather")
+        .unwrap()
+        .current_dir(root)
+        .args(["--use-gitignore", "--no-open", "."])
+        .assert()
+        .success();
+
+    let out = fs::read_to_string(root.join("gather/output.txt")).unwrap();
+    assert!(
+        !out.contains("ROOT_BUILD"),
+        "/build is anchored to the root"
+    );
+    assert!(out.contains("NESTED_BUILD"), "src/build is not anchored");
+    assert!(!out.contains("ROOT_LOG"), "*.log is ignored");
+    assert!(out.contains("KEPT_LOG"), "!keep.log re-includes");
+    assert!(out.contains("NESTED_LOG"), "nested .gitignore re-includes");
+    assert!(!out.contains("SECRET"), "nested .gitignore ignores");
+    assert!(!out.contains("SCRATCH"), ".git/info/exclude applies");
+}
@@ -0,0 +1,73 @@
+//! graph モード E2E テスト
+//!
+//! - `mod` / `use` を含むミニクレートを作成
+//! - `gather --mode graph` で `gather/output.dot` が生成されることを確認
+//! - `.gather` の `[exclude]` がグラフにも効くことを確認
+
+use assert_cmd::Command;
+use std::fs;
+use tempfile::tempdir;
+
+#[test]
+fn graph_writes_dot_and_honors_excludes() {
+    let tmp = tempdir().unwrap();
+    let root = tmp.path();
+    fs::create_dir_all(root.join("src/gen")).unwrap();
+    fs::write(root.join("src/lib.rs"), "mod model;\nmod scanner;\n").unwrap();
+    fs::write(root.join("src/model.rs"), "pub struct Config;\n").unwrap();
+    fs::write(
+        root.join("src/scanner.rs"),
+        "use crate::model::Config;\n#[path = \"gen/table.rs\"]\nmod table;\n",
+    )
+    .unwrap();
+    fs::write(root.join("src/gen/table.rs"), "").unwrap();
+    fs::write(root.join(".gather"), "[exclude]\ngen/\ngather/\n").unwrap();
+
+    Command::cargo_bin("gather")
+        .unwrap()
+        .current_dir(root)
+        .args(["--mode", "graph", "--no-open", "."])
+        .assert()
+        .success();
+
+    let dot = fs::read_to_string(root.join("gather/output.dot")).unwrap();
+    assert!(dot.starts_with("digraph dependencies {"));
+    assert!(dot.contains("\"src/lib.rs\" -> \"src/model.rs\" [style=solid];"));
+    assert!(dot.contains("\"src/scanner.rs\" -> \"src/model.rs\" [style=dashed];"));
+    assert!(!dot.contains("table.rs"), "excluded files must not appear");
+}
+
+#[test]
+fn graph_format_mermaid_and_json() {
+    let tmp = tempdir().unwrap();
+    let root = tmp.path();
+    fs::write(root.join("main.rs"), "mod util;\n").unwrap();
+    fs::write(root.join("util.rs"), "").unwrap();
+
+    let run = |fmt: &str| {
+        Command::cargo_bin("gather")
+            .unwrap()
+            .current_dir(root)
+            .args(["--mode", "graph", "--graph-format", fmt, "--no-open", "."])
+            .assert()
+            .success();
+    };
+
+    run("mermaid");
+    let md = fs::read_to_string(root.join("gather/output.md")).unwrap();
+    assert!(md.starts_with("```mermaid\nflowchart LR\n"));
+    assert!(md.contains("n0[\"main.rs\"]"));
+    assert!(md.contains("n0 --> n1"));
+
+    run("json");
+    let v: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(root.join("gather/output.json")).unwrap())
+            .unwrap();
+    assert_eq!(v["nodes"][1]["language"], "rust");
+    assert_eq!(v["nodes"][0]["path"], "main.rs");
+    assert_eq!(
+        v["nodes"][0]["edges"],
+        serde_json::json!([{"to": "util.rs", "kind": "mod"}])
+    );
+    assert_eq!(v["nodes"][1]["edges"], serde_json::json!([]));
+}
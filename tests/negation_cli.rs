@@ -0,0 +1,50 @@
+//! `[exclude]` / `[skip]` の `!pattern` による再包含 (後に書いた行ほど優先)
+
+use assert_cmd::Command;
+use std::fs;
+use tempfile::tempdir;
+
+#[test]
+fn negated_patterns_reinclude_in_tree_and_bodies() {
+    let tmp = tempdir().unwrap();
+    let root = tmp.path();
+
+    fs::create_dir_all(root.join("tests/fixtures")).unwrap();
+    fs::write(root.join("tests/fixtures/README.md"), "FIXTURE_README\n").unwrap();
+    fs::write(root.join("tests/fixtures/data.json"), "FIXTURE_DATA\n").unwrap();
+    fs::write(root.join("a.lock"), "LOCK_A\n").unwrap();
+    fs::write(root.join("Cargo.lock"), "LOCK_CARGO\n").unwrap();
+    fs::write(
+        root.join(".gather"),
+        r#"
+[exclude]
+gather/
+.gather
+tests/fixtures/
+!tests/fixtures/README.md
+
+[skip]
+*.lock
+!Cargo.lock
+"#,
+    )
+    .unwrap();
+
+    Command::cargo_bin("gather")
+        .unwrap()
+        .current_dir(root)
+        .args(["--no-open", "."])
+        .assert()
+        .success();
+
+    let out = fs::read_to_string(root.join("gather/output.txt")).unwrap();
+    assert!(
+        out.contains("    fixtures/\n        README.md\n"),
+        "tree: {out}"
+    );
+    assert!(out.contains("FIXTURE_README"), "re-included body");
+    assert!(!out.contains("data.json"), "still excluded");
+    assert!(out.contains("a.lock   [omitted:pattern]"), "still skipped");
+    assert!(out.contains("LOCK_CARGO"), "re-included from [skip]");
+    assert!(!out.contains("LOCK_A"));
+}
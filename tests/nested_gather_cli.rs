@@ -0,0 +1,49 @@
+//! サブディレクトリの `.gather` はその配下だけに効き、ルートの設定に重なる
+
+use assert_cmd::Command;
+use std::fs;
+use tempfile::tempdir;
+
+#[test]
+fn nested_gather_overrides_patterns_and_settings_for_its_subtree() {
+    let tmp = tempdir().unwrap();
+    let root = tmp.path();
+
+    fs::create_dir_all(root.join("web/dist")).unwrap();
+    fs::create_dir_all(root.join("dist")).unwrap();
+    fs::write(
+        root.join(".gather"),
+        "[settings]\nmax_lines = 100\n\n[exclude]\ngather/\n.gather\n*.snap\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("web/.gather"),
+        "[settings]\nmax_lines = 2\n\n[exclude]\ndist/\n!*.snap\n",
+    )
+    .unwrap();
+
+    fs::write(root.join("dist/root.js"), "ROOT_DIST\n").unwrap();
+    fs::write(root.join("web/dist/bundle.js"), "WEB_DIST\n").unwrap();
+    fs::write(root.join("root.snap"), "ROOT_SNAP\n").unwrap();
+    fs::write(root.join("web/ui.snap"), "WEB_SNAP\n").unwrap();
+    fs::write(root.join("long.txt"), "r1\nr2\nr3\n").unwrap();
+    fs::write(root.join("web/long.txt"), "w1\nw2\nw3\n").unwrap();
+
+    Command::cargo_bin("gather")
+        .unwrap()
+        .current_dir(root)
+        .args(["--no-open", "."])
+        .assert()
+        .success();
+
+    let out = fs::read_to_string(root.join("gather/output.txt")).unwrap();
+    assert!(
+        out.contains("ROOT_DIST"),
+        "dist/ is only excluded under web/"
+    );
+    assert!(!out.contains("WEB_DIST"));
+    assert!(!out.contains("ROOT_SNAP"));
+    assert!(out.contains("WEB_SNAP"), "web/.gather re-includes *.snap");
+    assert!(out.contains("r3"), "root max_lines");
+    assert!(!out.contains("w3"), "web/ max_lines = 2");
+}
@@ -45,4 +45,134 @@ fn outline_runs_and_creates_output() {
         content.contains("**fn** baz"),
         "expected '**fn** baz' in outline"
     );
+
+    // 行範囲が付与されるか
+    assert!(
+        content.contains("src/lib.rs:L4"),
+        "expected line range 'src/lib.rs:L4' in outline"
+    );
+}
+
+#[test]
+fn outline_detail_signatures_renders_fn_signature() {
+    let tmp = tempdir().unwrap();
+    let root = tmp.path();
+    fs::write(
+        root.join("lib.rs"),
+        "pub fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n",
+    )
+    .unwrap();
+
+    Command::cargo_bin("gather")
+        .unwrap()
+        .current_dir(root)
+        .args(["--mode", "outline", "--outline-detail", "signatures", "."])
+        .assert()
+        .success();
+
+    let content = fs::read_to_string(root.join("gather/output.txt")).unwrap();
+    assert!(
+        content.contains("pub fn add(a: i32, b: i32) -> i32"),
+        "expected fn signature in outline"
+    );
+    assert!(!content.contains("a + b"), "fn body must be elided");
+}
+
+#[test]
+fn outline_layout_flat_uses_qualified_paths() {
+    let tmp = tempdir().unwrap();
+    let root = tmp.path();
+    fs::write(
+        root.join("lib.rs"),
+        "pub mod net {\n    pub fn connect() {}\n}\n",
+    )
+    .unwrap();
+
+    Command::cargo_bin("gather")
+        .unwrap()
+        .current_dir(root)
+        .args(["--mode", "outline", "--outline-layout", "flat", "."])
+        .assert()
+        .success();
+
+    let content = fs::read_to_string(root.join("gather/output.txt")).unwrap();
+    assert!(
+        content.contains("- **fn** net::connect"),
+        "expected qualified path at top level in flat layout"
+    );
+    assert!(
+        !content.contains("  - **fn**"),
+        "flat layout must not indent"
+    );
+}
+
+#[test]
+fn outline_prints_doc_summary_beneath_symbol() {
+    let tmp = tempdir().unwrap();
+    let root = tmp.path();
+    fs::write(
+        root.join("lib.rs"),
+        "/// Adds two numbers.\n///\n/// Overflow panics in debug builds.\npub fn add() {}\n",
+    )
+    .unwrap();
+
+    let run = |extra: &[&str]| {
+        Command::cargo_bin("gather")
+            .unwrap()
+            .current_dir(root)
+            .args(["--mode", "outline"])
+            .args(extra)
+            .arg(".")
+            .assert()
+            .success();
+        fs::read_to_string(root.join("gather/output.txt")).unwrap()
+    };
+
+    let summary = run(&[]);
+    assert!(
+        summary.contains("- **fn** add (lib.rs:L4)\n  Adds two numbers.\n"),
+        "expected first paragraph beneath symbol, got:\n{summary}"
+    );
+    assert!(
+        !summary.contains("Overflow"),
+        "summary must stop at blank line"
+    );
+
+    let full = run(&["--outline-docs", "full"]);
+    assert!(full.contains("  Overflow panics in debug builds."));
+}
+
+#[test]
+fn outline_respects_gather_filters_and_natural_order() {
+    let tmp = tempdir().unwrap();
+    let root = tmp.path();
+    for dir in ["src", "target/debug", "node_modules/pkg"] {
+        fs::create_dir_all(root.join(dir)).unwrap();
+    }
+    fs::write(root.join("src/f10.rs"), "pub fn ten() {}\n").unwrap();
+    fs::write(root.join("src/f2.rs"), "pub fn two() {}\n").unwrap();
+    fs::write(root.join("src/notes.md"), "# Notes\n").unwrap();
+    fs::write(root.join("target/debug/build.rs"), "pub fn built() {}\n").unwrap();
+    fs::write(
+        root.join("node_modules/pkg/index.js"),
+        "export function dep() {}\n",
+    )
+    .unwrap();
+    fs::write(root.join(".gitignore"), "node_modules/\n").unwrap();
+    fs::write(root.join(".gather"), "[exclude]\ntarget/\n").unwrap();
+
+    Command::cargo_bin("gather")
+        .unwrap()
+        .current_dir(root)
+        .args(["--mode", "outline", "--use-gitignore", "-i", "*.rs", "."])
+        .assert()
+        .success();
+
+    let content = fs::read_to_string(root.join("gather/output.txt")).unwrap();
+    assert!(!content.contains("built"), "[exclude] must apply");
+    assert!(!content.contains("dep"), ".gitignore must apply");
+    assert!(!content.contains("Notes"), "-i filter must apply");
+    let two = content.find("### src/f2.rs").expect("f2 listed");
+    let ten = content.find("### src/f10.rs").expect("f10 listed");
+    assert!(two < ten, "natural sort: f2 before f10");
 }
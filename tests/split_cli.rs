@@ -0,0 +1,92 @@
+//! `--split` による出力分割の E2E テスト
+//!
+//! - 上限を超えると `output_part1.txt`, `output_part2.txt`, … に分割される
+//! - 1 ファイルのブロックはパートをまたがない
+//! - 各パートの先頭にツリー (または ファイル一覧) が繰り返される
+
+use assert_cmd::Command;
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+
+fn setup(root: &Path, gather: &str) {
+    for name in ["a", "b", "c"] {
+        let body: String = (0..20).map(|i| format!("{name} line {i}\n")).collect();
+        fs::write(root.join(format!("{name}.txt")), body).unwrap();
+    }
+    fs::write(root.join(".gather"), gather).unwrap();
+}
+
+#[test]
+fn split_by_lines_repeats_tree_per_part() {
+    let tmp = tempdir().unwrap();
+    let root = tmp.path();
+    setup(root, "[exclude]\ngather/\n.gather\n");
+
+    Command::cargo_bin("gather")
+        .unwrap()
+        .current_dir(root)
+        .args(["--no-open", "--split", "40lines", "."])
+        .assert()
+        .success();
+
+    let out = root.join("gather");
+    assert!(!out.join("output.txt").exists());
+    let parts: Vec<String> = (1..=3)
+        .map(|n| fs::read_to_string(out.join(format!("output_part{n}.txt"))).unwrap())
+        .collect();
+    assert!(!out.join("output_part4.txt").exists());
+
+    for (k, part) in parts.iter().enumerate() {
+        assert!(
+            part.starts_with(&format!("## Part {}/3\n", k + 1)),
+            "{part}"
+        );
+        assert!(part.contains("a.txt\nb.txt\nc.txt\n"), "tree is repeated");
+    }
+    // 各ファイルのブロックは丸ごと 1 つのパートに入る
+    for (part, name) in parts.iter().zip(["a", "b", "c"]) {
+        assert!(part.contains(&format!("### {name}.txt")));
+        assert!(part.contains(&format!("{name} line 0\n")));
+        assert!(part.contains(&format!("{name} line 19\n")));
+    }
+}
+
+#[test]
+fn split_header_index_lists_files_of_the_part() {
+    let tmp = tempdir().unwrap();
+    let root = tmp.path();
+    setup(
+        root,
+        "[settings]\nsplit = 400b\nsplit_header = index\n\n[exclude]\ngather/\n.gather\n",
+    );
+
+    Command::cargo_bin("gather")
+        .unwrap()
+        .current_dir(root)
+        .args(["--no-open", "."])
+        .assert()
+        .success();
+
+    let part1 = fs::read_to_string(root.join("gather/output_part1.txt")).unwrap();
+    assert!(part1.contains("Files in this part:\n- a.txt\n"), "{part1}");
+    assert!(!part1.contains("```\na.txt"), "tree is replaced by index");
+    assert!(root.join("gather/output_part2.txt").exists());
+}
+
+#[test]
+fn small_output_is_not_split() {
+    let tmp = tempdir().unwrap();
+    let root = tmp.path();
+    setup(root, "[exclude]\ngather/\n.gather\n");
+
+    Command::cargo_bin("gather")
+        .unwrap()
+        .current_dir(root)
+        .args(["--no-open", "--split", "1mb", "."])
+        .assert()
+        .success();
+
+    assert!(root.join("gather/output.txt").exists());
+    assert!(!root.join("gather/output_part1.txt").exists());
+}
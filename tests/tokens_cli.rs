@@ -0,0 +1,34 @@
+//! トークン数レポートの E2E テスト
+//!
+//! - サマリー (stderr) に合計トークン数と重いファイルが出ること
+//! - `--tree-tokens` でツリーに `[tokens:N]` が付くこと
+
+use assert_cmd::Command;
+use predicates::str::contains;
+use std::fs;
+use tempfile::tempdir;
+
+#[test]
+fn summary_reports_tokens_and_tree_is_annotated() {
+    let tmp = tempdir().unwrap();
+    let root = tmp.path();
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(root.join("src/big.rs"), "x".repeat(400)).unwrap();
+    fs::write(root.join("src/small.rs"), "y").unwrap();
+    fs::write(root.join(".gather"), "[exclude]\ngather/\n.gather\n").unwrap();
+
+    Command::cargo_bin("gather")
+        .unwrap()
+        .current_dir(root)
+        .args(["--no-open", "--tree-tokens", "."])
+        .assert()
+        .success()
+        .stderr(contains("- Tokens: "))
+        .stderr(contains("(estimated, chars/4)"))
+        .stderr(contains("- Heaviest files:\n  - src/big.rs: 108 tokens"));
+
+    let out = fs::read_to_string(root.join("gather/output.txt")).unwrap();
+    // "### src/big.rs\n```rust\n" (23) + 400 + "\n```\n\n" (6) = 429 chars → 108
+    assert!(out.contains("big.rs   [tokens:108]"), "got:\n{out}");
+    assert!(out.contains("src/   [tokens:"), "directories carry totals");
+}